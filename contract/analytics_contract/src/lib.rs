@@ -0,0 +1,125 @@
+#![no_std]
+
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Vec};
+
+pub use storage_types::*;
+
+const MAX_SERIES_EPOCHS: u64 = 100;
+
+#[contract]
+pub struct AnalyticsContract;
+
+#[contractimpl]
+impl AnalyticsContract {
+    /// Initialize the aggregator; epochs start at the current ledger time
+    pub fn initialize(env: Env, admin: Address, epoch_length: u64) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+        if epoch_length == 0 {
+            panic!("Epoch length must be positive");
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::EpochLength, &epoch_length);
+        env.storage().instance().set(&DataKey::Genesis, &env.ledger().timestamp());
+        env.storage().instance().set(&DataKey::Metrics, &Vec::<Symbol>::new(&env));
+    }
+
+    /// Allow a protocol contract to report metrics
+    pub fn add_reporter(env: Env, reporter: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().set(&DataKey::Reporter(reporter), &true);
+    }
+
+    /// Stop accepting reports from a contract
+    pub fn remove_reporter(env: Env, reporter: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().remove(&DataKey::Reporter(reporter));
+    }
+
+    /// Add `delta` to a metric. Counters only grow; gauges such as active subscriptions
+    /// report negative deltas when they shrink.
+    pub fn report(env: Env, reporter: Address, metric: Symbol, delta: i128) {
+        reporter.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Reporter(reporter.clone())) {
+            panic!("Not a registered reporter");
+        }
+
+        let mut metrics: Vec<Symbol> = env.storage().instance().get(&DataKey::Metrics).unwrap();
+        if !metrics.contains(&metric) {
+            metrics.push_back(metric.clone());
+            env.storage().instance().set(&DataKey::Metrics, &metrics);
+        }
+
+        let epoch = current_epoch(&env);
+        add(&env, &DataKey::Total(metric.clone()), delta);
+        add(&env, &DataKey::EpochValue(epoch, metric.clone()), delta);
+        add(&env, &DataKey::ReporterTotal(reporter, metric), delta);
+    }
+
+    /// Get the all-time value of a metric
+    pub fn get_total(env: Env, metric: Symbol) -> i128 {
+        read(&env, &DataKey::Total(metric))
+    }
+
+    /// Get the sum of deltas reported for a metric during one epoch
+    pub fn get_epoch_value(env: Env, epoch: u64, metric: Symbol) -> i128 {
+        read(&env, &DataKey::EpochValue(epoch, metric))
+    }
+
+    /// Get per-epoch values for a metric over `[from_epoch, to_epoch]` (at most 100 epochs)
+    pub fn get_epoch_series(env: Env, metric: Symbol, from_epoch: u64, to_epoch: u64) -> Vec<i128> {
+        if from_epoch > to_epoch || to_epoch - from_epoch >= MAX_SERIES_EPOCHS {
+            panic!("Invalid epoch range");
+        }
+
+        let mut series = Vec::new(&env);
+        for epoch in from_epoch..=to_epoch {
+            series.push_back(read(&env, &DataKey::EpochValue(epoch, metric.clone())));
+        }
+        series
+    }
+
+    /// Get the all-time value of a metric as reported by one contract
+    pub fn get_reporter_total(env: Env, reporter: Address, metric: Symbol) -> i128 {
+        read(&env, &DataKey::ReporterTotal(reporter, metric))
+    }
+
+    /// List every metric that has been reported
+    pub fn get_metrics(env: Env) -> Vec<Symbol> {
+        env.storage().instance().get(&DataKey::Metrics).unwrap()
+    }
+
+    /// Get the epoch the current ledger time falls into
+    pub fn get_current_epoch(env: Env) -> u64 {
+        current_epoch(&env)
+    }
+}
+
+fn read_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+fn current_epoch(env: &Env) -> u64 {
+    let genesis: u64 = env.storage().instance().get(&DataKey::Genesis).unwrap();
+    let epoch_length: u64 = env.storage().instance().get(&DataKey::EpochLength).unwrap();
+    (env.ledger().timestamp() - genesis) / epoch_length
+}
+
+fn read(env: &Env, key: &DataKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn add(env: &Env, key: &DataKey, delta: i128) {
+    let value = read(env, key);
+    env.storage().persistent().set(key, &(value + delta));
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    EpochLength,
+    Genesis,
+    Metrics,
+    Reporter(Address),
+    Total(Symbol),
+    EpochValue(u64, Symbol),        // (Epoch, Metric) -> sum of deltas reported in the epoch
+    ReporterTotal(Address, Symbol), // (Reporter, Metric) -> per-contract breakdown
+}
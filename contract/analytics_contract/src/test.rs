@@ -0,0 +1,72 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, Vec,
+};
+
+fn create_analytics_contract<'a>(e: &Env, admin: &Address) -> AnalyticsContractClient<'a> {
+    let contract = AnalyticsContractClient::new(e, &e.register(AnalyticsContract, ()));
+    contract.initialize(admin, &86400);
+    contract
+}
+
+#[test]
+fn test_report_and_epoch_rollups() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+
+    let admin = Address::generate(&env);
+    let subscriptions = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let contract = create_analytics_contract(&env, &admin);
+
+    contract.add_reporter(&subscriptions);
+    contract.add_reporter(&governance);
+
+    contract.report(&subscriptions, &symbol_short!("volume"), &1000);
+    contract.report(&subscriptions, &symbol_short!("active"), &1);
+
+    env.ledger().set_timestamp(1_000_000 + 2 * 86400);
+    contract.report(&subscriptions, &symbol_short!("volume"), &500);
+    contract.report(&subscriptions, &symbol_short!("active"), &-1);
+    contract.report(&governance, &symbol_short!("passed"), &1);
+
+    assert_eq!(contract.get_current_epoch(), 2);
+    assert_eq!(contract.get_total(&symbol_short!("volume")), 1500);
+    assert_eq!(contract.get_total(&symbol_short!("active")), 0);
+    assert_eq!(
+        contract.get_epoch_series(&symbol_short!("volume"), &0, &2),
+        Vec::from_array(&env, [1000, 0, 500])
+    );
+    assert_eq!(contract.get_reporter_total(&governance, &symbol_short!("passed")), 1);
+    assert_eq!(contract.get_metrics().len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Not a registered reporter")]
+fn test_unregistered_reporter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let contract = create_analytics_contract(&env, &admin);
+
+    contract.report(&stranger, &symbol_short!("volume"), &1);
+}
+
+#[test]
+#[should_panic(expected = "Invalid epoch range")]
+fn test_series_range_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract = create_analytics_contract(&env, &admin);
+
+    contract.get_epoch_series(&symbol_short!("volume"), &0, &100);
+}
@@ -58,7 +58,8 @@ impl StakingContract {
 
         // Transfer staking tokens from user to contract
         let token_client = token::Client::new(&env, &config.staking_token);
-        token_client.transfer(&user, &env.current_contract_address(), &amount);
+        let contract_address = env.current_contract_address();
+        token_client.transfer(&user, &contract_address, &amount);
 
         let mut user_info = read_user_info(&env, &user).unwrap_or(UserInfo {
             amount: 0,
@@ -305,6 +306,7 @@ impl StakingContract {
             &(new_wasm_hash.clone(), unlock_time),
         );
 
+        #[allow(deprecated)]
         env.events().publish(
             (Symbol::new(&env, "UpgradeScheduled"),),
             (new_wasm_hash, unlock_time),
@@ -318,6 +320,7 @@ impl StakingContract {
         config.admin.require_auth();
 
         env.storage().instance().remove(&DataKey::UpgradeTimelock);
+        #[allow(deprecated)]
         env.events()
             .publish((Symbol::new(&env, "UpgradeCancelled"),), ());
         extend_instance(&env);
@@ -348,6 +351,7 @@ impl StakingContract {
         env.deployer()
             .update_current_contract_wasm(new_wasm_hash.clone());
 
+        #[allow(deprecated)]
         env.events()
             .publish((Symbol::new(&env, "Upgraded"),), new_wasm_hash);
     }
@@ -368,6 +372,7 @@ impl StakingContract {
         env.storage()
             .instance()
             .set(&DataKey::Version, &new_version);
+        #[allow(deprecated)]
         env.events().publish(
             (Symbol::new(&env, "StateMigrated"),),
             (current_version, new_version),
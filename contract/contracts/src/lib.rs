@@ -6,3 +6,5 @@ pub mod types;
 
 #[cfg(test)]
 mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
@@ -0,0 +1,28 @@
+use soroban_sdk::{Address, Env};
+
+use crate::contract::{StakingContract, StakingContractClient};
+use crate::storage::{read_total_shares, read_user_info};
+use crate::types::UserInfo;
+
+/// Register and initialize a staking contract
+pub fn create_staking_contract<'a>(
+    env: &Env,
+    admin: &Address,
+    staking_token: &Address,
+    reward_token: &Address,
+    reward_rate: i128,
+) -> StakingContractClient<'a> {
+    let client = StakingContractClient::new(env, &env.register(StakingContract, ()));
+    client.initialize(admin, staking_token, reward_token, &reward_rate);
+    client
+}
+
+/// Read a staker's position from contract storage
+pub fn user_info(env: &Env, contract: &Address, user: &Address) -> Option<UserInfo> {
+    env.as_contract(contract, || read_user_info(env, user))
+}
+
+/// Read the total shares from contract storage
+pub fn total_shares(env: &Env, contract: &Address) -> i128 {
+    env.as_contract(contract, || read_total_shares(env))
+}
@@ -0,0 +1,32 @@
+use soroban_sdk::{contractclient, symbol_short, Address, Env, Symbol};
+
+use crate::storage::*;
+
+/// Subset of the analytics aggregator interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "AnalyticsClient")]
+pub trait AnalyticsInterface {
+    fn report(env: Env, reporter: Address, metric: Symbol, delta: i128);
+}
+
+pub const PROPOSALS_PASSED: Symbol = symbol_short!("passed");
+
+/// Report a metric delta to the aggregator if one is configured.
+/// Failures are ignored so analytics can never block governance.
+pub fn report(env: &Env, metric: Symbol, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+
+    if let Some(analytics) = env
+        .storage()
+        .instance()
+        .get::<DataKey, Address>(&DataKey::Analytics)
+    {
+        let _ = AnalyticsClient::new(env, &analytics).try_report(
+            &env.current_contract_address(),
+            &metric,
+            &delta,
+        );
+    }
+}
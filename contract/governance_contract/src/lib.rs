@@ -2,6 +2,8 @@
 
 use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Vec, token, log};
 
+mod analytics;
+mod registry;
 mod storage;
 use storage::*;
 
@@ -42,6 +44,18 @@ impl GovernanceContract {
         env.storage().instance().set(&DataKey::CategorySettings(category_id), &settings);
     }
 
+    pub fn set_analytics(env: Env, analytics: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Analytics, &analytics);
+    }
+
+    pub fn set_registry(env: Env, registry: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Registry, &registry);
+    }
+
     pub fn create_proposal(
         env: Env,
         proposer: Address,
@@ -206,6 +220,7 @@ impl GovernanceContract {
                 proposal.status = ProposalStatus::Queued;
                 let timelock: u64 = env.storage().instance().get(&DataKey::TimelockDuration).unwrap();
                 proposal.eta = env.ledger().timestamp() + timelock;
+                analytics::report(&env, analytics::PROPOSALS_PASSED, 1);
             } else {
                 proposal.status = ProposalStatus::Defeated;
             }
@@ -233,6 +248,10 @@ impl GovernanceContract {
 
         proposal.status = ProposalStatus::Executed;
         env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+
+        if let GovernanceAction::SetParameter(namespace, key, value) = proposal.action.clone() {
+            registry::set_param(&env, namespace, key, value);
+        }
         
         env.events().publish((symbol_short!("execute"), proposal_id), proposal.action);
     }
@@ -0,0 +1,21 @@
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+use crate::storage::*;
+
+/// Subset of the parameter registry interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn set_param(env: Env, namespace: Symbol, key: Symbol, value: ParamValue);
+}
+
+/// Write an executed parameter proposal to the registry
+pub fn set_param(env: &Env, namespace: Symbol, key: Symbol, value: ParamValue) {
+    let registry: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Registry)
+        .expect("Registry not configured");
+
+    RegistryClient::new(env, &registry).set_param(&namespace, &key, &value);
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Vec, String};
+use soroban_sdk::{contracttype, Address, Vec, String, Symbol};
 
 #[derive(Clone)]
 #[contracttype]
@@ -13,6 +13,8 @@ pub enum DataKey {
     UserDelegation(Address), // User -> Delegatee
     UserVotesRevoked(u32, Address),
     CategorySettings(u32), // CategoryID -> CategorySettings
+    Analytics,
+    Registry,
 }
 
 #[derive(Clone)]
@@ -31,6 +33,18 @@ pub enum GovernanceAction {
     FeeChange(u32),  // New fee in basis points
     ParameterChange(String, u32), // Param name, new value
     EmergencyAction,
+    SetParameter(Symbol, Symbol, ParamValue), // Namespace, key, value written to the parameter registry
+}
+
+// Mirrors the parameter registry's value type
+#[derive(Clone)]
+#[contracttype]
+pub enum ParamValue {
+    U32(u32),
+    U64(u64),
+    I128(i128),
+    Bool(bool),
+    Address(Address),
 }
 
 #[derive(Clone)]
@@ -0,0 +1,146 @@
+#![no_std]
+
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol};
+
+pub use storage_types::*;
+
+/// Membership badges. One badge per holder, issued and extended by protocol contracts
+/// while the holder's subscription is paid up. Badges cannot be transferred, so other
+/// contracts can gate on `is_member` without calling the subscription contract.
+#[contract]
+pub struct MembershipBadgeContract;
+
+#[contractimpl]
+impl MembershipBadgeContract {
+    /// Initialize the badge collection with admin and display metadata
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+        env.storage().instance().set(&DataKey::TotalIssued, &0u32);
+    }
+
+    /// Allow a protocol contract to issue and revoke badges
+    pub fn add_minter(env: Env, minter: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().set(&DataKey::Minter(minter), &true);
+    }
+
+    /// Revoke a protocol contract's issuing rights
+    pub fn remove_minter(env: Env, minter: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().remove(&DataKey::Minter(minter));
+    }
+
+    /// Mint a badge to `to`, or extend their existing badge to `expires_at`
+    pub fn issue(env: Env, minter: Address, to: Address, plan_id: u32, expires_at: u64) {
+        minter.require_auth();
+        require_minter(&env, &minter);
+
+        let badge = match read_badge(&env, &to) {
+            Some(badge) => Badge {
+                issuer: minter.clone(),
+                plan_id,
+                issued_at: badge.issued_at,
+                expires_at,
+            },
+            None => {
+                let issued: u32 = env.storage().instance().get(&DataKey::TotalIssued).unwrap();
+                env.storage().instance().set(&DataKey::TotalIssued, &(issued + 1));
+
+                Badge {
+                    issuer: minter.clone(),
+                    plan_id,
+                    issued_at: env.ledger().timestamp(),
+                    expires_at,
+                }
+            }
+        };
+
+        env.storage().persistent().set(&DataKey::Badge(to.clone()), &badge);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((Symbol::new(&env, "issue"), minter, to), expires_at);
+    }
+
+    /// Remove a holder's badge
+    pub fn revoke(env: Env, minter: Address, holder: Address) {
+        minter.require_auth();
+        require_minter(&env, &minter);
+
+        if read_badge(&env, &holder).is_none() {
+            return;
+        }
+
+        env.storage().persistent().remove(&DataKey::Badge(holder.clone()));
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((Symbol::new(&env, "revoke"), minter, holder), ());
+    }
+
+    /// Check whether an address holds an unexpired badge
+    pub fn is_member(env: Env, holder: Address) -> bool {
+        match read_badge(&env, &holder) {
+            Some(badge) => env.ledger().timestamp() <= badge.expires_at,
+            None => false,
+        }
+    }
+
+    /// Get a holder's badge, expired or not
+    pub fn get_badge(env: Env, holder: Address) -> Option<Badge> {
+        read_badge(&env, &holder)
+    }
+
+    /// Get the number of distinct holders ever issued a badge
+    pub fn total_issued(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::TotalIssued).unwrap()
+    }
+
+    /// Check whether an address may issue and revoke
+    pub fn is_minter(env: Env, minter: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Minter(minter))
+            .unwrap_or(false)
+    }
+
+    pub fn name(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Name).unwrap()
+    }
+
+    pub fn symbol(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Symbol).unwrap()
+    }
+}
+
+fn read_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+fn read_badge(env: &Env, holder: &Address) -> Option<Badge> {
+    env.storage().persistent().get(&DataKey::Badge(holder.clone()))
+}
+
+fn require_minter(env: &Env, minter: &Address) {
+    let is_minter: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Minter(minter.clone()))
+        .unwrap_or(false);
+    if !is_minter {
+        panic!("Not a minter");
+    }
+}
@@ -0,0 +1,21 @@
+use soroban_sdk::{contracttype, Address};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Name,
+    Symbol,
+    TotalIssued,
+    Minter(Address),
+    Badge(Address),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Badge {
+    pub issuer: Address,
+    pub plan_id: u32,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
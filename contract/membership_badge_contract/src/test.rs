@@ -0,0 +1,61 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+fn create_badge_contract<'a>(e: &Env, admin: &Address) -> MembershipBadgeContractClient<'a> {
+    let contract = MembershipBadgeContractClient::new(e, &e.register(MembershipBadgeContract, ()));
+    contract.initialize(
+        admin,
+        &String::from_str(e, "Gatherraa Membership"),
+        &String::from_str(e, "GMEM"),
+    );
+    contract
+}
+
+#[test]
+fn test_issue_extend_and_expire() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract = create_badge_contract(&env, &admin);
+
+    contract.add_minter(&minter);
+    contract.issue(&minter, &user, &1, &1000);
+    assert!(contract.is_member(&user));
+
+    env.ledger().set_timestamp(500);
+    contract.issue(&minter, &user, &2, &2000);
+
+    let badge = contract.get_badge(&user).unwrap();
+    assert_eq!(badge.issued_at, 0);
+    assert_eq!(badge.plan_id, 2);
+    assert_eq!(badge.expires_at, 2000);
+    assert_eq!(contract.total_issued(), 1);
+
+    env.ledger().set_timestamp(2001);
+    assert!(!contract.is_member(&user));
+
+    contract.revoke(&minter, &user);
+    assert!(contract.get_badge(&user).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Not a minter")]
+fn test_issue_requires_minter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract = create_badge_contract(&env, &admin);
+
+    contract.issue(&stranger, &user, &1, &1000);
+}
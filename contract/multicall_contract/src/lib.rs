@@ -0,0 +1,89 @@
+#![no_std]
+
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Val, Vec};
+
+pub use storage_types::*;
+
+const MAX_CALLS: u32 = 16;
+
+#[contract]
+pub struct MulticallContract;
+
+#[contractimpl]
+impl MulticallContract {
+    /// Initialize the contract with the admin managing allowed targets
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    /// Allow batches to call a protocol contract
+    pub fn add_target(env: Env, target: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().set(&DataKey::AllowedTarget(target), &true);
+    }
+
+    /// Stop allowing batches to call a contract
+    pub fn remove_target(env: Env, target: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().remove(&DataKey::AllowedTarget(target));
+    }
+
+    /// Execute calls in order and return their results.
+    ///
+    /// The caller authorizes the whole batch once; nested `require_auth` checks in the targets
+    /// are satisfied by the same authorization tree. If any call fails the transaction traps
+    /// and every earlier call in the batch is rolled back with it.
+    pub fn execute(env: Env, caller: Address, calls: Vec<Call>) -> Vec<Val> {
+        caller.require_auth();
+
+        if calls.is_empty() {
+            panic!("No calls");
+        }
+        if calls.len() > MAX_CALLS {
+            panic!("Too many calls");
+        }
+
+        let mut results = Vec::new(&env);
+        for call in calls.iter() {
+            if !is_allowed(&env, &call.contract) {
+                panic!("Target not allowed");
+            }
+            let result = env.invoke_contract::<Val>(&call.contract, &call.function, call.args);
+            results.push_back(result);
+        }
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (Symbol::new(&env, "batch_executed"), caller),
+            calls.len(),
+        );
+
+        results
+    }
+
+    /// Check whether a contract may be called from a batch
+    pub fn is_target_allowed(env: Env, target: Address) -> bool {
+        is_allowed(&env, &target)
+    }
+}
+
+fn read_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+fn is_allowed(env: &Env, target: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AllowedTarget(target.clone()))
+        .unwrap_or(false)
+}
@@ -0,0 +1,16 @@
+use soroban_sdk::{contracttype, Address, Symbol, Val, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    AllowedTarget(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Call {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
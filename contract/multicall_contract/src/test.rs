@@ -0,0 +1,186 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, MockAuth, MockAuthInvoke},
+    vec, Address, Env, IntoVal, Vec,
+};
+
+/// Stand-in protocol contract keeping a per-user counter
+#[contract]
+pub struct MockProtocol;
+
+#[contractimpl]
+impl MockProtocol {
+    pub fn bump(env: Env, user: Address, by: u32) -> u32 {
+        user.require_auth();
+        if by == 0 {
+            panic!("zero bump");
+        }
+        let value: u32 = env.storage().persistent().get(&user).unwrap_or(0) + by;
+        env.storage().persistent().set(&user, &value);
+        value
+    }
+
+    pub fn value(env: Env, user: Address) -> u32 {
+        env.storage().persistent().get(&user).unwrap_or(0)
+    }
+}
+
+fn setup<'a>(env: &Env) -> (MulticallContractClient<'a>, MockProtocolClient<'a>) {
+    let admin = Address::generate(env);
+    let multicall = MulticallContractClient::new(env, &env.register(MulticallContract, ()));
+    let protocol = MockProtocolClient::new(env, &env.register(MockProtocol, ()));
+    multicall.initialize(&admin);
+    multicall.add_target(&protocol.address);
+    (multicall, protocol)
+}
+
+fn bump_call(env: &Env, protocol: &Address, user: &Address, by: u32) -> Call {
+    Call {
+        contract: protocol.clone(),
+        function: symbol_short!("bump"),
+        args: vec![env, user.into_val(env), by.into_val(env)],
+    }
+}
+
+#[test]
+fn test_execute_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (multicall, protocol) = setup(&env);
+    let user = Address::generate(&env);
+
+    let calls = Vec::from_array(
+        &env,
+        [
+            bump_call(&env, &protocol.address, &user, 1),
+            bump_call(&env, &protocol.address, &user, 2),
+        ],
+    );
+    let results = multicall.execute(&user, &calls);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(protocol.value(&user), 3);
+}
+
+#[test]
+fn test_batch_authorized_by_one_tree() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (multicall, protocol) = setup(&env);
+    let user = Address::generate(&env);
+
+    let calls = Vec::from_array(
+        &env,
+        [
+            bump_call(&env, &protocol.address, &user, 1),
+            bump_call(&env, &protocol.address, &user, 2),
+        ],
+    );
+    let bump_one = MockAuthInvoke {
+        contract: &protocol.address,
+        fn_name: "bump",
+        args: (user.clone(), 1u32).into_val(&env),
+        sub_invokes: &[],
+    };
+    let bump_two = MockAuthInvoke {
+        contract: &protocol.address,
+        fn_name: "bump",
+        args: (user.clone(), 2u32).into_val(&env),
+        sub_invokes: &[],
+    };
+
+    // A tree that leaves out one of the nested calls does not cover the batch
+    env.mock_auths(&[MockAuth {
+        address: &user,
+        invoke: &MockAuthInvoke {
+            contract: &multicall.address,
+            fn_name: "execute",
+            args: (user.clone(), calls.clone()).into_val(&env),
+            sub_invokes: core::slice::from_ref(&bump_one),
+        },
+    }]);
+    assert!(multicall.try_execute(&user, &calls).is_err());
+
+    env.mock_auths(&[MockAuth {
+        address: &user,
+        invoke: &MockAuthInvoke {
+            contract: &multicall.address,
+            fn_name: "execute",
+            args: (user.clone(), calls.clone()).into_val(&env),
+            sub_invokes: &[bump_one, bump_two],
+        },
+    }]);
+    multicall.execute(&user, &calls);
+
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            user.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    multicall.address.clone(),
+                    symbol_short!("execute"),
+                    (user.clone(), calls.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![
+                    AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            protocol.address.clone(),
+                            symbol_short!("bump"),
+                            (user.clone(), 1u32).into_val(&env),
+                        )),
+                        sub_invocations: std::vec![],
+                    },
+                    AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            protocol.address.clone(),
+                            symbol_short!("bump"),
+                            (user.clone(), 2u32).into_val(&env),
+                        )),
+                        sub_invocations: std::vec![],
+                    },
+                ],
+            }
+        )]
+    );
+    assert_eq!(protocol.value(&user), 3);
+}
+
+#[test]
+fn test_failed_call_reverts_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (multicall, protocol) = setup(&env);
+    let user = Address::generate(&env);
+
+    let calls = Vec::from_array(
+        &env,
+        [
+            bump_call(&env, &protocol.address, &user, 5),
+            bump_call(&env, &protocol.address, &user, 0),
+        ],
+    );
+
+    assert!(multicall.try_execute(&user, &calls).is_err());
+    assert_eq!(protocol.value(&user), 0);
+}
+
+#[test]
+#[should_panic(expected = "Target not allowed")]
+fn test_unknown_target_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (multicall, protocol) = setup(&env);
+    let user = Address::generate(&env);
+
+    multicall.remove_target(&protocol.address);
+    multicall.execute(&user, &Vec::from_array(&env, [bump_call(&env, &protocol.address, &user, 1)]));
+}
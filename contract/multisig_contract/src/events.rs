@@ -0,0 +1,56 @@
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ProposalCreatedEvent {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ApprovalChangedEvent {
+    pub proposal_id: u32,
+    pub member: Address,
+    pub approved: bool,
+    pub approvals: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ProposalExecutedEvent {
+    pub proposal_id: u32,
+    pub executor: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ProposalCancelledEvent {
+    pub proposal_id: u32,
+    pub cancelled_by: Address,
+}
+
+#[allow(deprecated)]
+pub fn emit_proposal_created(env: &Env, event: ProposalCreatedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "proposal_created"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_approval_changed(env: &Env, event: ApprovalChangedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "approval_changed"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_proposal_executed(env: &Env, event: ProposalExecutedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "proposal_executed"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_proposal_cancelled(env: &Env, event: ProposalCancelledEvent) {
+    env.events()
+        .publish((Symbol::new(env, "proposal_cancelled"),), event);
+}
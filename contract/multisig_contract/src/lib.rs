@@ -0,0 +1,292 @@
+#![no_std]
+
+mod events;
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, IntoVal, Val, Vec};
+
+pub use storage_types::*;
+
+/// Proposals that are not executed within this window can no longer be executed
+const PROPOSAL_LIFETIME: u64 = 7 * 86400;
+
+#[contract]
+pub struct MultisigContract;
+
+#[contractimpl]
+impl MultisigContract {
+    /// Initialize the wallet with its members and approval threshold; every member must sign
+    pub fn initialize(env: Env, members: Vec<Address>, threshold: u32) {
+        if env.storage().instance().has(&DataKey::Members) {
+            panic!("Already initialized");
+        }
+
+        for (i, member) in members.iter().enumerate() {
+            if members.first_index_of(&member) != Some(i as u32) {
+                panic!("Duplicate member");
+            }
+            member.require_auth();
+        }
+        validate_threshold(threshold, members.len());
+
+        env.storage().instance().set(&DataKey::Members, &members);
+        env.storage().instance().set(&DataKey::Threshold, &threshold);
+        env.storage().instance().set(&DataKey::ProposalCount, &0u32);
+    }
+
+    /// Propose an action; the proposer's approval is recorded immediately
+    pub fn propose(env: Env, proposer: Address, action: ProposalAction) -> u32 {
+        proposer.require_auth();
+        require_member(&env, &proposer);
+
+        let mut count: u32 = env.storage().instance().get(&DataKey::ProposalCount).unwrap();
+        count += 1;
+
+        let now = env.ledger().timestamp();
+        let proposal = Proposal {
+            id: count,
+            proposer: proposer.clone(),
+            action,
+            approvals: 1,
+            status: ProposalStatus::Pending,
+            created_at: now,
+            expires_at: now + PROPOSAL_LIFETIME,
+        };
+
+        env.storage().persistent().set(&DataKey::Proposal(count), &proposal);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approval(count, proposer.clone()), &member_epoch(&env, &proposer));
+        env.storage().instance().set(&DataKey::ProposalCount, &count);
+
+        events::emit_proposal_created(
+            &env,
+            events::ProposalCreatedEvent {
+                proposal_id: count,
+                proposer,
+                expires_at: proposal.expires_at,
+            },
+        );
+
+        count
+    }
+
+    /// Approve a pending proposal
+    pub fn approve(env: Env, member: Address, proposal_id: u32) {
+        member.require_auth();
+        require_member(&env, &member);
+
+        let mut proposal = read_pending_proposal(&env, proposal_id);
+
+        if has_current_approval(&env, proposal_id, &member) {
+            panic!("Already approved");
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approval(proposal_id, member.clone()), &member_epoch(&env, &member));
+
+        proposal.approvals = count_approvals(&env, proposal_id);
+        env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+
+        events::emit_approval_changed(
+            &env,
+            events::ApprovalChangedEvent {
+                proposal_id,
+                member,
+                approved: true,
+                approvals: proposal.approvals,
+            },
+        );
+    }
+
+    /// Withdraw a previously given approval
+    pub fn revoke_approval(env: Env, member: Address, proposal_id: u32) {
+        member.require_auth();
+
+        let mut proposal = read_pending_proposal(&env, proposal_id);
+
+        if !has_current_approval(&env, proposal_id, &member) {
+            panic!("Not approved");
+        }
+        env.storage().persistent().remove(&DataKey::Approval(proposal_id, member.clone()));
+
+        proposal.approvals = count_approvals(&env, proposal_id);
+        env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+
+        events::emit_approval_changed(
+            &env,
+            events::ApprovalChangedEvent {
+                proposal_id,
+                member,
+                approved: false,
+                approvals: proposal.approvals,
+            },
+        );
+    }
+
+    /// Execute a proposal once approvals from current members reach the threshold.
+    /// Returns the target call's result for `Invoke` actions.
+    pub fn execute(env: Env, executor: Address, proposal_id: u32) -> Val {
+        executor.require_auth();
+        require_member(&env, &executor);
+
+        let mut proposal = read_pending_proposal(&env, proposal_id);
+        let threshold: u32 = env.storage().instance().get(&DataKey::Threshold).unwrap();
+
+        let approvals = count_approvals(&env, proposal_id);
+        if approvals < threshold {
+            panic!("Threshold not met");
+        }
+
+        // Mark executed before the external call so the proposal cannot be replayed
+        proposal.status = ProposalStatus::Executed;
+        proposal.approvals = approvals;
+        env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+
+        let result = match proposal.action {
+            ProposalAction::Invoke(target, function, args) => {
+                env.invoke_contract::<Val>(&target, &function, args)
+            }
+            ProposalAction::AddMember(member) => {
+                let mut members = read_members(&env);
+                if members.contains(&member) {
+                    panic!("Already a member");
+                }
+                // A returning member starts a new epoch, so approvals from before their removal lapse
+                let epoch = member_epoch(&env, &member) + 1;
+                env.storage().persistent().set(&DataKey::MemberEpoch(member.clone()), &epoch);
+                members.push_back(member);
+                env.storage().instance().set(&DataKey::Members, &members);
+                ().into_val(&env)
+            }
+            ProposalAction::RemoveMember(member) => {
+                let mut members = read_members(&env);
+                let index = members.first_index_of(&member).expect("Not a member");
+                members.remove(index);
+                validate_threshold(threshold, members.len());
+                env.storage().instance().set(&DataKey::Members, &members);
+                ().into_val(&env)
+            }
+            ProposalAction::SetThreshold(new_threshold) => {
+                validate_threshold(new_threshold, read_members(&env).len());
+                env.storage().instance().set(&DataKey::Threshold, &new_threshold);
+                ().into_val(&env)
+            }
+        };
+
+        events::emit_proposal_executed(
+            &env,
+            events::ProposalExecutedEvent {
+                proposal_id,
+                executor,
+            },
+        );
+
+        result
+    }
+
+    /// Cancel a pending proposal; only the proposer can cancel
+    pub fn cancel(env: Env, proposer: Address, proposal_id: u32) {
+        proposer.require_auth();
+
+        let mut proposal = read_pending_proposal(&env, proposal_id);
+        if proposal.proposer != proposer {
+            panic!("Only proposer can cancel");
+        }
+
+        proposal.status = ProposalStatus::Cancelled;
+        env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+
+        events::emit_proposal_cancelled(
+            &env,
+            events::ProposalCancelledEvent {
+                proposal_id,
+                cancelled_by: proposer,
+            },
+        );
+    }
+
+    /// Get proposal details
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        env.storage().persistent().get(&DataKey::Proposal(proposal_id))
+    }
+
+    /// Check whether a member has approved a proposal during their current membership
+    pub fn has_approved(env: Env, proposal_id: u32, member: Address) -> bool {
+        has_current_approval(&env, proposal_id, &member)
+    }
+
+    /// Get the current members
+    pub fn get_members(env: Env) -> Vec<Address> {
+        read_members(&env)
+    }
+
+    /// Get the current approval threshold
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Threshold).unwrap()
+    }
+}
+
+fn read_members(env: &Env) -> Vec<Address> {
+    env.storage().instance().get(&DataKey::Members).unwrap()
+}
+
+fn require_member(env: &Env, address: &Address) {
+    if !read_members(env).contains(address) {
+        panic!("Not a member");
+    }
+}
+
+fn read_pending_proposal(env: &Env, proposal_id: u32) -> Proposal {
+    let proposal: Proposal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .expect("Proposal not found");
+
+    if proposal.status != ProposalStatus::Pending {
+        panic!("Proposal not pending");
+    }
+    if env.ledger().timestamp() > proposal.expires_at {
+        panic!("Proposal expired");
+    }
+
+    proposal
+}
+
+fn member_epoch(env: &Env, member: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MemberEpoch(member.clone()))
+        .unwrap_or(0)
+}
+
+fn has_current_approval(env: &Env, proposal_id: u32, member: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<DataKey, u32>(&DataKey::Approval(proposal_id, member.clone()))
+        == Some(member_epoch(env, member))
+}
+
+/// Count approvals from the current member set, so approvals of removed members no longer
+/// count, even if they are added back
+fn count_approvals(env: &Env, proposal_id: u32) -> u32 {
+    let mut approvals = 0;
+    for member in read_members(env).iter() {
+        if has_current_approval(env, proposal_id, &member) {
+            approvals += 1;
+        }
+    }
+    approvals
+}
+
+fn validate_threshold(threshold: u32, member_count: u32) {
+    if threshold == 0 || threshold > member_count {
+        panic!("Invalid threshold");
+    }
+}
@@ -0,0 +1,42 @@
+use soroban_sdk::{contracttype, Address, Symbol, Val, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Members,
+    Threshold,
+    ProposalCount,
+    Proposal(u32),
+    Approval(u32, Address), // (ProposalID, Member) -> membership epoch the approval was given in
+    MemberEpoch(Address),   // Bumped each time the address is added as a member
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum ProposalAction {
+    /// Call `function` on `target` with `args`, e.g. pause, fee update or upgrade on a protocol contract
+    Invoke(Address, Symbol, Vec<Val>),
+    AddMember(Address),
+    RemoveMember(Address),
+    SetThreshold(u32),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[contracttype]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub action: ProposalAction,
+    pub approvals: u32,
+    pub status: ProposalStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
@@ -0,0 +1,292 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, IntoVal, Vec,
+};
+
+/// Minimal admin-gated contract standing in for a protocol contract
+#[contract]
+pub struct MockProtocol;
+
+#[contractimpl]
+impl MockProtocol {
+    pub fn init(env: Env, admin: Address) {
+        env.storage().instance().set(&symbol_short!("admin"), &admin);
+    }
+
+    pub fn set_fee(env: Env, fee_bps: u32) {
+        let admin: Address = env.storage().instance().get(&symbol_short!("admin")).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&symbol_short!("fee"), &fee_bps);
+    }
+
+    pub fn fee(env: Env) -> u32 {
+        env.storage().instance().get(&symbol_short!("fee")).unwrap_or(0)
+    }
+}
+
+fn setup<'a>(env: &Env, threshold: u32) -> (MultisigContractClient<'a>, Vec<Address>) {
+    let members = Vec::from_array(
+        env,
+        [
+            Address::generate(env),
+            Address::generate(env),
+            Address::generate(env),
+        ],
+    );
+    let contract = MultisigContractClient::new(env, &env.register(MultisigContract, ()));
+    contract.initialize(&members, &threshold);
+    (contract, members)
+}
+
+#[test]
+fn test_execute_admin_call_after_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 2);
+    let protocol = MockProtocolClient::new(&env, &env.register(MockProtocol, ()));
+    protocol.init(&contract.address);
+
+    let action = ProposalAction::Invoke(
+        protocol.address.clone(),
+        symbol_short!("set_fee"),
+        vec![&env, 250u32.into_val(&env)],
+    );
+    let proposal_id = contract.propose(&members.get(0).unwrap(), &action);
+    assert_eq!(contract.get_proposal(&proposal_id).unwrap().approvals, 1);
+
+    contract.approve(&members.get(1).unwrap(), &proposal_id);
+    contract.execute(&members.get(2).unwrap(), &proposal_id);
+
+    assert_eq!(protocol.fee(), 250);
+    assert_eq!(
+        contract.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Executed
+    );
+}
+
+#[test]
+#[should_panic(expected = "Threshold not met")]
+fn test_execute_below_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 2);
+    let proposal_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::SetThreshold(3));
+    contract.execute(&members.get(0).unwrap(), &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Proposal not pending")]
+fn test_cannot_execute_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 1);
+    let proposal_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::SetThreshold(2));
+    contract.execute(&members.get(0).unwrap(), &proposal_id);
+    contract.execute(&members.get(0).unwrap(), &proposal_id);
+}
+
+#[test]
+fn test_membership_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 2);
+    let newcomer = Address::generate(&env);
+
+    let add_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::AddMember(newcomer.clone()));
+    contract.approve(&members.get(1).unwrap(), &add_id);
+    contract.execute(&members.get(0).unwrap(), &add_id);
+    assert_eq!(contract.get_members().len(), 4);
+
+    let remove_id = contract.propose(&newcomer, &ProposalAction::RemoveMember(members.get(2).unwrap()));
+    contract.approve(&members.get(0).unwrap(), &remove_id);
+    contract.execute(&newcomer, &remove_id);
+    assert!(!contract.get_members().contains(members.get(2).unwrap()));
+
+    let threshold_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::SetThreshold(3));
+    contract.approve(&newcomer, &threshold_id);
+    contract.execute(&newcomer, &threshold_id);
+    assert_eq!(contract.get_threshold(), 3);
+}
+
+#[test]
+fn test_removed_member_approval_not_counted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 2);
+
+    let pending_id = contract.propose(&members.get(2).unwrap(), &ProposalAction::SetThreshold(1));
+
+    let remove_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::RemoveMember(members.get(2).unwrap()));
+    contract.approve(&members.get(1).unwrap(), &remove_id);
+    contract.execute(&members.get(0).unwrap(), &remove_id);
+
+    assert!(contract.try_execute(&members.get(0).unwrap(), &pending_id).is_err());
+}
+
+#[test]
+fn test_readded_member_approval_not_counted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 2);
+    let returning = members.get(2).unwrap();
+
+    let pending_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::SetThreshold(1));
+    contract.approve(&returning, &pending_id);
+
+    let remove_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::RemoveMember(returning.clone()));
+    contract.approve(&members.get(1).unwrap(), &remove_id);
+    contract.execute(&members.get(0).unwrap(), &remove_id);
+
+    let add_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::AddMember(returning.clone()));
+    contract.approve(&members.get(1).unwrap(), &add_id);
+    contract.execute(&members.get(0).unwrap(), &add_id);
+
+    // The approval given before the removal no longer counts
+    assert!(!contract.has_approved(&pending_id, &returning));
+    assert!(contract.try_execute(&members.get(0).unwrap(), &pending_id).is_err());
+
+    contract.approve(&returning, &pending_id);
+    contract.execute(&members.get(0).unwrap(), &pending_id);
+    assert_eq!(contract.get_threshold(), 1);
+}
+
+#[test]
+fn test_initialize_requires_every_member() {
+    let env = Env::default();
+
+    let members = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+    let contract = MultisigContractClient::new(&env, &env.register(MultisigContract, ()));
+    assert!(contract.try_initialize(&members, &1).is_err());
+
+    env.mock_all_auths();
+    contract.initialize(&members, &1);
+    let signers: Vec<Address> = Vec::from_iter(&env, env.auths().into_iter().map(|(address, _)| address));
+    assert_eq!(signers, members);
+}
+
+#[test]
+fn test_revoke_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 2);
+    let proposal_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::SetThreshold(3));
+
+    contract.approve(&members.get(1).unwrap(), &proposal_id);
+    contract.revoke_approval(&members.get(1).unwrap(), &proposal_id);
+    assert!(!contract.has_approved(&proposal_id, &members.get(1).unwrap()));
+    assert_eq!(contract.get_proposal(&proposal_id).unwrap().approvals, 1);
+
+    contract.cancel(&members.get(0).unwrap(), &proposal_id);
+    assert_eq!(
+        contract.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Cancelled
+    );
+}
+
+#[test]
+#[should_panic(expected = "Proposal expired")]
+fn test_expired_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, members) = setup(&env, 1);
+    let proposal_id = contract.propose(&members.get(0).unwrap(), &ProposalAction::SetThreshold(2));
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 8 * 86400);
+    contract.execute(&members.get(0).unwrap(), &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Invalid threshold")]
+fn test_invalid_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    setup(&env, 4);
+}
+
+mod properties {
+    use crate::testutils::{approve_all, create_multisig};
+    use crate::{ProposalAction, ProposalStatus};
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+    use test_utils::setup_env;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// A proposal executes exactly when the approvals standing from current members reach
+        /// the threshold, whatever order approvals are given and withdrawn in.
+        #[test]
+        fn prop_execution_requires_threshold(
+            member_count in 1u32..7,
+            threshold in 1u32..7,
+            votes in proptest::collection::vec((0u32..7, any::<bool>()), 0..16),
+        ) {
+            let threshold = threshold.min(member_count);
+            let env = setup_env();
+            let (client, members) = create_multisig(&env, member_count, threshold);
+            let proposer = members.get(0).unwrap();
+            let proposal_id = client.propose(&proposer, &ProposalAction::SetThreshold(threshold));
+
+            for (index, approve) in votes.iter() {
+                let Some(member) = members.get(*index) else { continue };
+                if *approve {
+                    let _ = client.try_approve(&member, &proposal_id);
+                } else {
+                    let _ = client.try_revoke_approval(&member, &proposal_id);
+                }
+            }
+
+            let approvals = members.iter().filter(|member| client.has_approved(&proposal_id, member)).count() as u32;
+            prop_assert_eq!(client.get_proposal(&proposal_id).unwrap().approvals, approvals);
+            prop_assert_eq!(client.try_execute(&proposer, &proposal_id).is_ok(), approvals >= threshold);
+        }
+
+        /// Membership and threshold changes, executed or rejected, never leave the wallet with a
+        /// threshold it cannot meet or a duplicated member.
+        #[test]
+        fn prop_threshold_stays_reachable(
+            member_count in 1u32..5,
+            actions in proptest::collection::vec((0u8..3, 0u32..6), 1..10),
+        ) {
+            let env = setup_env();
+            let (client, _) = create_multisig(&env, member_count, 1);
+
+            for (kind, value) in actions.iter() {
+                let members = client.get_members();
+                let action = match kind {
+                    0 => ProposalAction::AddMember(Address::generate(&env)),
+                    1 => ProposalAction::RemoveMember(members.get(value % members.len()).unwrap()),
+                    _ => ProposalAction::SetThreshold(*value),
+                };
+
+                let proposer = members.get(0).unwrap();
+                let proposal_id = client.propose(&proposer, &action);
+                approve_all(&client, proposal_id);
+                let executed = client.try_execute(&proposer, &proposal_id).is_ok();
+
+                let members = client.get_members();
+                let threshold = client.get_threshold();
+                prop_assert!(threshold >= 1 && threshold <= members.len());
+                for (i, member) in members.iter().enumerate() {
+                    prop_assert_eq!(members.first_index_of(&member), Some(i as u32));
+                }
+                let status = client.get_proposal(&proposal_id).unwrap().status;
+                prop_assert_eq!(status == ProposalStatus::Executed, executed);
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use soroban_sdk::{testutils::Address as _, Address, Env, Vec};
+
+use crate::{MultisigContract, MultisigContractClient};
+
+/// Register and initialize a multisig with `member_count` fresh members
+pub fn create_multisig<'a>(env: &Env, member_count: u32, threshold: u32) -> (MultisigContractClient<'a>, Vec<Address>) {
+    let mut members = Vec::new(env);
+    for _ in 0..member_count {
+        members.push_back(Address::generate(env));
+    }
+
+    let client = MultisigContractClient::new(env, &env.register(MultisigContract, ()));
+    client.initialize(&members, &threshold);
+    (client, members)
+}
+
+/// Approve a proposal from every current member that has not approved it yet. Requires mocked auths.
+pub fn approve_all(client: &MultisigContractClient, proposal_id: u32) {
+    for member in client.get_members().iter() {
+        if !client.has_approved(&proposal_id, &member) {
+            client.approve(&member, &proposal_id);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RaffleEnteredEvent {
+    pub raffle_id: u32,
+    pub entrant: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RaffleRevealedEvent {
+    pub raffle_id: u32,
+    pub seed: BytesN<32>,
+    pub draw_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RaffleDrawnEvent {
+    pub raffle_id: u32,
+    pub winner_count: u32,
+    pub root: BytesN<32>,
+}
+
+#[allow(deprecated)]
+pub fn emit_raffle_entered(env: &Env, event: RaffleEnteredEvent) {
+    env.events()
+        .publish((Symbol::new(env, "raffle_entered"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_raffle_revealed(env: &Env, event: RaffleRevealedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "raffle_revealed"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_raffle_drawn(env: &Env, event: RaffleDrawnEvent) {
+    env.events()
+        .publish((Symbol::new(env, "raffle_drawn"),), event);
+}
@@ -0,0 +1,292 @@
+#![no_std]
+
+mod events;
+mod merkle;
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+use soroban_sdk::{contract, contractimpl, token, Address, Bytes, BytesN, Env, Vec};
+
+pub use storage_types::*;
+
+const MAX_ENTRANTS: u32 = 500;
+const MAX_WINNERS: u32 = 100;
+const DRAW_DELAY_LEDGERS: u32 = 1;
+
+#[contract]
+pub struct RaffleContract;
+
+#[contractimpl]
+impl RaffleContract {
+    /// Initialize the contract with an admin
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::NextRaffleId, &1u32);
+    }
+
+    /// Create a raffle. `config.commitment` is the sha256 of a secret seed revealed at draw time.
+    pub fn create_raffle(env: Env, organizer: Address, config: RaffleConfig) -> u32 {
+        organizer.require_auth();
+
+        if config.entry_fee < 0 || config.allocation <= 0 {
+            panic!("Invalid amounts");
+        }
+        if config.winner_count == 0 || config.winner_count > MAX_WINNERS {
+            panic!("Invalid winner count");
+        }
+        if config.entry_deadline <= env.ledger().timestamp()
+            || config.reveal_deadline <= config.entry_deadline
+        {
+            panic!("Invalid deadlines");
+        }
+
+        let raffle_id: u32 = env.storage().instance().get(&DataKey::NextRaffleId).unwrap();
+
+        let raffle = Raffle {
+            raffle_id,
+            organizer,
+            config,
+            entrant_count: 0,
+            status: RaffleStatus::Open,
+            root: None,
+        };
+
+        env.storage().persistent().set(&DataKey::Raffle(raffle_id), &raffle);
+        env.storage().persistent().set(&DataKey::Entrants(raffle_id), &Vec::<Address>::new(&env));
+        env.storage().instance().set(&DataKey::NextRaffleId, &(raffle_id + 1));
+
+        raffle_id
+    }
+
+    /// Enter a raffle, paying the entry fee or stake if one is set
+    pub fn enter(env: Env, raffle_id: u32, entrant: Address) {
+        entrant.require_auth();
+
+        let mut raffle = read_raffle(&env, raffle_id);
+        if raffle.status != RaffleStatus::Open || env.ledger().timestamp() >= raffle.config.entry_deadline {
+            panic!("Raffle not open");
+        }
+        if raffle.entrant_count >= MAX_ENTRANTS {
+            panic!("Raffle full");
+        }
+        if env.storage().persistent().has(&DataKey::Entered(raffle_id, entrant.clone())) {
+            panic!("Already entered");
+        }
+
+        if raffle.config.entry_fee > 0 {
+            let token_client = token::TokenClient::new(&env, &raffle.config.token);
+            token_client.transfer(&entrant, env.current_contract_address(), &raffle.config.entry_fee);
+        }
+
+        let mut entrants = read_entrants(&env, raffle_id);
+        entrants.push_back(entrant.clone());
+        raffle.entrant_count += 1;
+
+        env.storage().persistent().set(&DataKey::Entrants(raffle_id), &entrants);
+        env.storage().persistent().set(&DataKey::Entered(raffle_id, entrant.clone()), &true);
+        env.storage().persistent().set(&DataKey::Raffle(raffle_id), &raffle);
+
+        events::emit_raffle_entered(&env, events::RaffleEnteredEvent { raffle_id, entrant });
+    }
+
+    /// Reveal the committed seed, fixing the ledger the draw will take its entropy from.
+    ///
+    /// The draw itself runs in a later ledger whose PRNG output does not exist yet, so the
+    /// organizer cannot simulate the result before revealing. Once revealed, anyone can run
+    /// the draw, so the organizer can no longer abort it.
+    pub fn reveal(env: Env, raffle_id: u32, seed: BytesN<32>) {
+        let mut raffle = read_raffle(&env, raffle_id);
+        raffle.organizer.require_auth();
+
+        let current_time = env.ledger().timestamp();
+        if raffle.status != RaffleStatus::Open {
+            panic!("Raffle not open");
+        }
+        if current_time < raffle.config.entry_deadline {
+            panic!("Entry period not over");
+        }
+        if current_time > raffle.config.reveal_deadline {
+            panic!("Reveal deadline passed");
+        }
+        if raffle.entrant_count == 0 {
+            panic!("No entrants");
+        }
+
+        let seed_hash: BytesN<32> = env
+            .crypto()
+            .sha256(&Bytes::from_array(&env, &seed.to_array()))
+            .into();
+        if seed_hash != raffle.config.commitment {
+            panic!("Seed does not match commitment");
+        }
+
+        let draw_ledger = env.ledger().sequence() + DRAW_DELAY_LEDGERS;
+        raffle.status = RaffleStatus::Revealed;
+        env.storage().persistent().set(&DataKey::Raffle(raffle_id), &raffle);
+        env.storage().persistent().set(
+            &DataKey::Reveal(raffle_id),
+            &Reveal {
+                seed: seed.clone(),
+                draw_ledger,
+            },
+        );
+
+        events::emit_raffle_revealed(
+            &env,
+            events::RaffleRevealedEvent {
+                raffle_id,
+                seed,
+                draw_ledger,
+            },
+        );
+    }
+
+    /// Draw winners from a revealed raffle. Callable by anyone once the draw ledger is reached.
+    ///
+    /// The revealed seed is mixed with the PRNG output and sequence of the ledger the draw runs
+    /// in. The remaining trust assumption is the network's per-ledger PRNG seed, which neither
+    /// the organizer nor entrants can predict ahead of the ledger closing.
+    pub fn draw(env: Env, raffle_id: u32) -> BytesN<32> {
+        let mut raffle = read_raffle(&env, raffle_id);
+        if raffle.status != RaffleStatus::Revealed {
+            panic!("Raffle not revealed");
+        }
+
+        let reveal: Reveal = env.storage().persistent().get(&DataKey::Reveal(raffle_id)).unwrap();
+        let sequence = env.ledger().sequence();
+        if sequence < reveal.draw_ledger {
+            panic!("Draw ledger not reached");
+        }
+
+        let ledger_entropy: BytesN<32> = env.prng().gen();
+        let mut mixed = Bytes::from_array(&env, &reveal.seed.to_array());
+        mixed.append(&Bytes::from_array(&env, &ledger_entropy.to_array()));
+        mixed.append(&Bytes::from_array(&env, &sequence.to_be_bytes()));
+        let final_seed: BytesN<32> = env.crypto().sha256(&mixed).into();
+        env.prng().seed(Bytes::from_array(&env, &final_seed.to_array()));
+
+        let mut entrants = read_entrants(&env, raffle_id);
+        env.prng().shuffle(&mut entrants);
+
+        let count = raffle.config.winner_count.min(entrants.len());
+        let winners = entrants.slice(0..count);
+
+        let root = merkle::root(&env, &winner_leaves(&env, &winners, raffle.config.allocation));
+
+        raffle.status = RaffleStatus::Drawn;
+        raffle.root = Some(root.clone());
+        env.storage().persistent().set(&DataKey::Raffle(raffle_id), &raffle);
+        env.storage().persistent().set(&DataKey::Winners(raffle_id), &winners);
+
+        if raffle.config.entry_fee > 0 && !raffle.config.is_stake {
+            let token_client = token::TokenClient::new(&env, &raffle.config.token);
+            token_client.transfer(
+                &env.current_contract_address(),
+                &raffle.organizer,
+                &(raffle.config.entry_fee * raffle.entrant_count as i128),
+            );
+        }
+
+        events::emit_raffle_drawn(
+            &env,
+            events::RaffleDrawnEvent {
+                raffle_id,
+                winner_count: count,
+                root: root.clone(),
+            },
+        );
+
+        root
+    }
+
+    /// Cancel a raffle before it is drawn; entrants can then reclaim their fees
+    pub fn cancel(env: Env, raffle_id: u32) {
+        let mut raffle = read_raffle(&env, raffle_id);
+        raffle.organizer.require_auth();
+
+        if raffle.status != RaffleStatus::Open {
+            panic!("Raffle not open");
+        }
+
+        raffle.status = RaffleStatus::Cancelled;
+        env.storage().persistent().set(&DataKey::Raffle(raffle_id), &raffle);
+    }
+
+    /// Reclaim an entry fee from a cancelled or never-revealed raffle, or a stake after the draw.
+    /// A revealed raffle is not refundable since anyone can complete its draw.
+    pub fn refund(env: Env, raffle_id: u32, entrant: Address) {
+        entrant.require_auth();
+
+        let raffle = read_raffle(&env, raffle_id);
+        let refundable = match raffle.status {
+            RaffleStatus::Cancelled => true,
+            RaffleStatus::Open => env.ledger().timestamp() > raffle.config.reveal_deadline,
+            RaffleStatus::Revealed => false,
+            RaffleStatus::Drawn => raffle.config.is_stake,
+        };
+        if !refundable || raffle.config.entry_fee == 0 {
+            panic!("Nothing to refund");
+        }
+        if !env.storage().persistent().has(&DataKey::Entered(raffle_id, entrant.clone())) {
+            panic!("Not an entrant");
+        }
+        if env.storage().persistent().has(&DataKey::Refunded(raffle_id, entrant.clone())) {
+            panic!("Already refunded");
+        }
+
+        env.storage().persistent().set(&DataKey::Refunded(raffle_id, entrant.clone()), &true);
+
+        let token_client = token::TokenClient::new(&env, &raffle.config.token);
+        token_client.transfer(&env.current_contract_address(), &entrant, &raffle.config.entry_fee);
+    }
+
+    /// Get raffle details, including the winners' Merkle root once drawn
+    pub fn get_raffle(env: Env, raffle_id: u32) -> Option<Raffle> {
+        env.storage().persistent().get(&DataKey::Raffle(raffle_id))
+    }
+
+    /// Get the drawn winners
+    pub fn get_winners(env: Env, raffle_id: u32) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Winners(raffle_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a winner's proof for claiming from a whitelist campaign using the raffle root
+    pub fn get_proof(env: Env, raffle_id: u32, winner: Address) -> Vec<BytesN<32>> {
+        let raffle = read_raffle(&env, raffle_id);
+        let winners = Self::get_winners(env.clone(), raffle_id);
+        let index = winners.first_index_of(&winner).expect("Not a winner");
+
+        merkle::proof(&env, &winner_leaves(&env, &winners, raffle.config.allocation), index)
+    }
+}
+
+fn read_raffle(env: &Env, raffle_id: u32) -> Raffle {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Raffle(raffle_id))
+        .expect("Raffle not found")
+}
+
+fn read_entrants(env: &Env, raffle_id: u32) -> Vec<Address> {
+    env.storage().persistent().get(&DataKey::Entrants(raffle_id)).unwrap()
+}
+
+fn winner_leaves(env: &Env, winners: &Vec<Address>, allocation: i128) -> Vec<BytesN<32>> {
+    let mut leaves = Vec::new(env);
+    for winner in winners.iter() {
+        leaves.push_back(merkle::hash_leaf(env, &winner, allocation));
+    }
+    leaves
+}
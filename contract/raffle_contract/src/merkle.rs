@@ -0,0 +1,62 @@
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+/// Leaf hash matching whitelist_contract's `hash_leaf`
+pub fn hash_leaf(env: &Env, address: &Address, amount: i128) -> BytesN<32> {
+    let mut bytes = address.to_xdr(env);
+    bytes.append(&amount.to_xdr(env));
+    env.crypto().sha256(&bytes).into()
+}
+
+/// Hash a pair in sorted order, as whitelist_contract's `merkle::verify` expects
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let mut data = [0u8; 64];
+    if a.to_array() < b.to_array() {
+        data[..32].copy_from_slice(&a.to_array());
+        data[32..].copy_from_slice(&b.to_array());
+    } else {
+        data[..32].copy_from_slice(&b.to_array());
+        data[32..].copy_from_slice(&a.to_array());
+    }
+    env.crypto().sha256(&Bytes::from_array(env, &data)).into()
+}
+
+/// Hash one tree level into the next; an unpaired last node is carried up unchanged
+fn next_level(env: &Env, level: &Vec<BytesN<32>>) -> Vec<BytesN<32>> {
+    let mut next = Vec::new(env);
+    let mut i = 0;
+    while i < level.len() {
+        let left = level.get(i).unwrap();
+        if i + 1 < level.len() {
+            next.push_back(hash_pair(env, &left, &level.get(i + 1).unwrap()));
+        } else {
+            next.push_back(left);
+        }
+        i += 2;
+    }
+    next
+}
+
+/// Compute the root over `leaves`
+pub fn root(env: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        level = next_level(env, &level);
+    }
+    level.get(0).expect("No leaves")
+}
+
+/// Build the proof for the leaf at `index`
+pub fn proof(env: &Env, leaves: &Vec<BytesN<32>>, index: u32) -> Vec<BytesN<32>> {
+    let mut proof = Vec::new(env);
+    let mut level = leaves.clone();
+    let mut index = index;
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            proof.push_back(level.get(sibling).unwrap());
+        }
+        level = next_level(env, &level);
+        index /= 2;
+    }
+    proof
+}
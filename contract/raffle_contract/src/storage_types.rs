@@ -0,0 +1,54 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    NextRaffleId,
+    Raffle(u32),
+    Entrants(u32),
+    Entered(u32, Address),
+    Winners(u32),
+    Refunded(u32, Address),
+    Reveal(u32),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[contracttype]
+pub enum RaffleStatus {
+    Open,
+    Revealed,
+    Drawn,
+    Cancelled,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct RaffleConfig {
+    pub token: Address,
+    pub entry_fee: i128,
+    pub is_stake: bool, // Stakes are returned to every entrant after the draw; fees go to the organizer
+    pub winner_count: u32,
+    pub allocation: i128, // Amount each winner can claim from the whitelist campaign
+    pub entry_deadline: u64,
+    pub reveal_deadline: u64,
+    pub commitment: BytesN<32>, // sha256 of the organizer's secret seed
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Raffle {
+    pub raffle_id: u32,
+    pub organizer: Address,
+    pub config: RaffleConfig,
+    pub entrant_count: u32,
+    pub status: RaffleStatus,
+    pub root: Option<BytesN<32>>,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Reveal {
+    pub seed: BytesN<32>,
+    pub draw_ledger: u32, // First ledger the draw can run in; its entropy is unknown at reveal time
+}
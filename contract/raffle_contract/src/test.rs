@@ -0,0 +1,246 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Bytes, BytesN, Env, Vec,
+};
+
+fn create_token_contract<'a>(e: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
+    token::StellarAssetClient::new(e, &e.register_stellar_asset_contract_v2(admin.clone()).address())
+}
+
+fn commitment(env: &Env, seed: &BytesN<32>) -> BytesN<32> {
+    env.crypto()
+        .sha256(&Bytes::from_array(env, &seed.to_array()))
+        .into()
+}
+
+/// Same check whitelist_contract runs when a winner claims
+fn verify(env: &Env, root: &BytesN<32>, leaf: BytesN<32>, proof: Vec<BytesN<32>>) -> bool {
+    let mut computed = leaf;
+    for node in proof.iter() {
+        let mut data = [0u8; 64];
+        if computed.to_array() < node.to_array() {
+            data[..32].copy_from_slice(&computed.to_array());
+            data[32..].copy_from_slice(&node.to_array());
+        } else {
+            data[..32].copy_from_slice(&node.to_array());
+            data[32..].copy_from_slice(&computed.to_array());
+        }
+        computed = env.crypto().sha256(&Bytes::from_array(env, &data)).into();
+    }
+    computed == *root
+}
+
+fn setup<'a>(
+    env: &Env,
+    entry_fee: i128,
+    is_stake: bool,
+) -> (RaffleContractClient<'a>, token::StellarAssetClient<'a>, Address, u32, BytesN<32>) {
+    let admin = Address::generate(env);
+    let organizer = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = create_token_contract(env, &token_admin);
+    let contract = RaffleContractClient::new(env, &env.register(RaffleContract, ()));
+    let seed = BytesN::from_array(env, &[7; 32]);
+
+    contract.initialize(&admin);
+    let raffle_id = contract.create_raffle(
+        &organizer,
+        &RaffleConfig {
+            token: token.address.clone(),
+            entry_fee,
+            is_stake,
+            winner_count: 3,
+            allocation: 500,
+            entry_deadline: 1000,
+            reveal_deadline: 2000,
+            commitment: commitment(env, &seed),
+        },
+    );
+
+    (contract, token, organizer, raffle_id, seed)
+}
+
+fn enter_many(
+    env: &Env,
+    contract: &RaffleContractClient,
+    token: &token::StellarAssetClient,
+    raffle_id: u32,
+    n: u32,
+) -> Vec<Address> {
+    let mut entrants = Vec::new(env);
+    for _ in 0..n {
+        let entrant = Address::generate(env);
+        token.mint(&entrant, &100);
+        contract.enter(&raffle_id, &entrant);
+        entrants.push_back(entrant);
+    }
+    entrants
+}
+
+#[test]
+fn test_draw_publishes_whitelist_root() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, organizer, raffle_id, seed) = setup(&env, 100, false);
+    let token_client = token::Client::new(&env, &token.address);
+    let entrants = enter_many(&env, &contract, &token, raffle_id, 6);
+
+    env.ledger().set_timestamp(1000);
+    contract.reveal(&raffle_id, &seed);
+    assert_eq!(contract.get_raffle(&raffle_id).unwrap().status, RaffleStatus::Revealed);
+
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    let root = contract.draw(&raffle_id);
+
+    let raffle = contract.get_raffle(&raffle_id).unwrap();
+    assert_eq!(raffle.status, RaffleStatus::Drawn);
+    assert_eq!(raffle.root, Some(root.clone()));
+    assert_eq!(token_client.balance(&organizer), 600);
+
+    let winners = contract.get_winners(&raffle_id);
+    assert_eq!(winners.len(), 3);
+    for winner in winners.iter() {
+        assert!(entrants.contains(&winner));
+        let leaf = merkle::hash_leaf(&env, &winner, 500);
+        assert!(verify(&env, &root, leaf, contract.get_proof(&raffle_id, &winner)));
+    }
+}
+
+#[test]
+fn test_stake_returned_after_draw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, raffle_id, seed) = setup(&env, 100, true);
+    let token_client = token::Client::new(&env, &token.address);
+    let entrants = enter_many(&env, &contract, &token, raffle_id, 2);
+
+    env.ledger().set_timestamp(1000);
+    contract.reveal(&raffle_id, &seed);
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    contract.draw(&raffle_id);
+
+    // Fewer entrants than winner slots: everyone wins
+    assert_eq!(contract.get_winners(&raffle_id).len(), 2);
+
+    let entrant = entrants.get(0).unwrap();
+    contract.refund(&raffle_id, &entrant);
+    assert_eq!(token_client.balance(&entrant), 100);
+}
+
+#[test]
+fn test_refund_when_never_revealed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, raffle_id, _seed) = setup(&env, 100, false);
+    let token_client = token::Client::new(&env, &token.address);
+    let entrants = enter_many(&env, &contract, &token, raffle_id, 1);
+
+    env.ledger().set_timestamp(2001);
+    let entrant = entrants.get(0).unwrap();
+    contract.refund(&raffle_id, &entrant);
+    assert_eq!(token_client.balance(&entrant), 100);
+}
+
+#[test]
+#[should_panic(expected = "Seed does not match commitment")]
+fn test_reveal_rejects_wrong_seed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, raffle_id, _seed) = setup(&env, 0, false);
+    enter_many(&env, &contract, &token, raffle_id, 2);
+
+    env.ledger().set_timestamp(1000);
+    contract.reveal(&raffle_id, &BytesN::from_array(&env, &[8; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Draw ledger not reached")]
+fn test_draw_waits_for_later_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, raffle_id, seed) = setup(&env, 0, false);
+    enter_many(&env, &contract, &token, raffle_id, 2);
+
+    env.ledger().set_timestamp(1000);
+    contract.reveal(&raffle_id, &seed);
+    contract.draw(&raffle_id);
+}
+
+#[test]
+#[should_panic(expected = "Nothing to refund")]
+fn test_no_refund_after_reveal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, raffle_id, seed) = setup(&env, 100, false);
+    let entrants = enter_many(&env, &contract, &token, raffle_id, 1);
+
+    env.ledger().set_timestamp(1000);
+    contract.reveal(&raffle_id, &seed);
+
+    // Past the reveal deadline the draw is still open to anyone, so no refund
+    env.ledger().set_timestamp(2001);
+    contract.refund(&raffle_id, &entrants.get(0).unwrap());
+}
+
+mod properties {
+    use crate::testutils::{create_open_raffle, enter_raffle, ENTRY_DEADLINE, REVEAL_DEADLINE};
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Ledger;
+    use test_utils::{create_token, set_time, setup_env};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        /// However a raffle ends (drawn, cancelled or never revealed), every entry fee ends up
+        /// with the organizer or back with its entrant, and the contract keeps nothing.
+        #[test]
+        fn prop_entry_fees_fully_paid_out(
+            entrant_count in 1u32..16,
+            entry_fee in 0i128..10_000,
+            is_stake in any::<bool>(),
+            winner_count in 1u32..8,
+            outcome in 0u8..3,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            let raffle = create_open_raffle(&env, &token.address, entry_fee, is_stake, winner_count);
+            let entrants = enter_raffle(&env, &raffle, &token.address, entry_fee, entrant_count);
+            prop_assert_eq!(token.balance(&raffle.contract.address), entry_fee * entrant_count as i128);
+
+            match outcome {
+                0 => {
+                    set_time(&env, ENTRY_DEADLINE);
+                    raffle.contract.reveal(&raffle.raffle_id, &raffle.seed);
+                    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+                    raffle.contract.draw(&raffle.raffle_id);
+
+                    let winners = raffle.contract.get_winners(&raffle.raffle_id);
+                    prop_assert_eq!(winners.len(), winner_count.min(entrant_count));
+                    for winner in winners.iter() {
+                        prop_assert!(entrants.contains(&winner));
+                    }
+                }
+                1 => raffle.contract.cancel(&raffle.raffle_id),
+                _ => set_time(&env, REVEAL_DEADLINE + 1),
+            }
+
+            for entrant in entrants.iter() {
+                let refunded = raffle.contract.try_refund(&raffle.raffle_id, entrant).is_ok();
+                prop_assert_eq!(token.balance(entrant), if refunded { entry_fee } else { 0 });
+            }
+
+            let paid_out: i128 = entrants.iter().map(|entrant| token.balance(entrant)).sum();
+            prop_assert_eq!(paid_out + token.balance(&raffle.organizer), entry_fee * entrant_count as i128);
+            prop_assert_eq!(token.balance(&raffle.contract.address), 0);
+        }
+    }
+}
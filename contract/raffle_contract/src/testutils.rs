@@ -0,0 +1,79 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token, Address, Bytes, BytesN, Env};
+
+use crate::{RaffleConfig, RaffleContract, RaffleContractClient};
+
+pub const ENTRY_DEADLINE: u64 = 1000;
+pub const REVEAL_DEADLINE: u64 = 2000;
+
+/// Register and initialize a raffle contract
+pub fn create_raffle_contract<'a>(env: &Env, admin: &Address) -> RaffleContractClient<'a> {
+    let client = RaffleContractClient::new(env, &env.register(RaffleContract, ()));
+    client.initialize(admin);
+    client
+}
+
+/// Commitment the organizer publishes for a seed
+pub fn commitment(env: &Env, seed: &BytesN<32>) -> BytesN<32> {
+    env.crypto()
+        .sha256(&Bytes::from_array(env, &seed.to_array()))
+        .into()
+}
+
+/// An open raffle with a fresh organizer and the seed behind its commitment
+pub struct RaffleFixture<'a> {
+    pub contract: RaffleContractClient<'a>,
+    pub raffle_id: u32,
+    pub organizer: Address,
+    pub seed: BytesN<32>,
+}
+
+/// Create a raffle closing entries at `ENTRY_DEADLINE` and reveals at `REVEAL_DEADLINE`.
+/// Requires mocked auths.
+pub fn create_open_raffle<'a>(
+    env: &Env,
+    token: &Address,
+    entry_fee: i128,
+    is_stake: bool,
+    winner_count: u32,
+) -> RaffleFixture<'a> {
+    let contract = create_raffle_contract(env, &Address::generate(env));
+    let organizer = Address::generate(env);
+    let seed = BytesN::from_array(env, &[7; 32]);
+
+    let raffle_id = contract.create_raffle(
+        &organizer,
+        &RaffleConfig {
+            token: token.clone(),
+            entry_fee,
+            is_stake,
+            winner_count,
+            allocation: 500,
+            entry_deadline: ENTRY_DEADLINE,
+            reveal_deadline: REVEAL_DEADLINE,
+            commitment: commitment(env, &seed),
+        },
+    );
+
+    RaffleFixture {
+        contract,
+        raffle_id,
+        organizer,
+        seed,
+    }
+}
+
+/// Mint the entry fee to `n` fresh entrants and enter them
+pub fn enter_raffle(env: &Env, fixture: &RaffleFixture, token: &Address, entry_fee: i128, n: u32) -> std::vec::Vec<Address> {
+    (0..n)
+        .map(|_| {
+            let entrant = Address::generate(env);
+            if entry_fee > 0 {
+                token::StellarAssetClient::new(env, token).mint(&entrant, &entry_fee);
+            }
+            fixture.contract.enter(&fixture.raffle_id, &entrant);
+            entrant
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub struct CodeRegisteredEvent {
+    pub code: Symbol,
+    pub owner: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct CodeStatusChangedEvent {
+    pub code: Symbol,
+    pub is_active: bool,
+    pub changed_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ReferralAttributedEvent {
+    pub user: Address,
+    pub code: Symbol,
+    pub referrer: Address,
+    pub consumer: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RewardAccruedEvent {
+    pub referrer: Address,
+    pub user: Address,
+    pub token: Address,
+    pub level: u32,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct EarningsWithdrawnEvent {
+    pub referrer: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[allow(deprecated)]
+pub fn emit_code_registered(env: &Env, event: CodeRegisteredEvent) {
+    env.events()
+        .publish((Symbol::new(env, "code_registered"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_code_status_changed(env: &Env, event: CodeStatusChangedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "code_status_changed"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_referral_attributed(env: &Env, event: ReferralAttributedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "referral_attributed"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_reward_accrued(env: &Env, event: RewardAccruedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "reward_accrued"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_earnings_withdrawn(env: &Env, event: EarningsWithdrawnEvent) {
+    env.events()
+        .publish((Symbol::new(env, "earnings_withdrawn"),), event);
+}
@@ -0,0 +1,361 @@
+#![no_std]
+
+mod events;
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+use soroban_sdk::{contract, contractimpl, token, Address, Env, Symbol, Vec};
+
+pub use storage_types::*;
+
+const BASIS_POINTS: u32 = 10_000;
+const MAX_LEVELS: u32 = 3;
+
+#[contract]
+pub struct ReferralContract;
+
+#[contractimpl]
+impl ReferralContract {
+    /// Initialize the contract with admin and the per-level reward shares (bps of each accrued reward)
+    pub fn initialize(env: Env, admin: Address, level_shares: Vec<u32>) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+        validate_level_shares(&level_shares);
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::LevelShares, &level_shares);
+    }
+
+    /// Replace the per-level reward shares
+    pub fn set_level_shares(env: Env, level_shares: Vec<u32>) {
+        read_admin(&env).require_auth();
+        validate_level_shares(&level_shares);
+
+        env.storage().instance().set(&DataKey::LevelShares, &level_shares);
+    }
+
+    /// Allow a protocol contract to attribute users and accrue rewards
+    pub fn add_consumer(env: Env, consumer: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().set(&DataKey::Consumer(consumer), &true);
+    }
+
+    /// Revoke a protocol contract's consumer rights
+    pub fn remove_consumer(env: Env, consumer: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().remove(&DataKey::Consumer(consumer));
+    }
+
+    /// Register a referral code owned by the caller (one code per owner)
+    pub fn register_code(env: Env, owner: Address, code: Symbol) {
+        owner.require_auth();
+
+        if env.storage().persistent().has(&DataKey::Code(code.clone())) {
+            panic!("Code already registered");
+        }
+        if env.storage().persistent().has(&DataKey::OwnerCode(owner.clone())) {
+            panic!("Owner already has a code");
+        }
+
+        let referral_code = ReferralCode {
+            code: code.clone(),
+            owner: owner.clone(),
+            is_active: true,
+            created_at: env.ledger().timestamp(),
+            referred_count: 0,
+        };
+
+        env.storage().persistent().set(&DataKey::Code(code.clone()), &referral_code);
+        env.storage().persistent().set(&DataKey::OwnerCode(owner.clone()), &code);
+
+        events::emit_code_registered(&env, events::CodeRegisteredEvent { code, owner });
+    }
+
+    /// Activate or deactivate a code; callable by the code owner or the admin
+    pub fn set_code_active(env: Env, caller: Address, code: Symbol, is_active: bool) {
+        caller.require_auth();
+
+        let mut referral_code = read_code(&env, &code);
+        if caller != referral_code.owner && caller != read_admin(&env) {
+            panic!("Not authorized");
+        }
+
+        referral_code.is_active = is_active;
+        env.storage().persistent().set(&DataKey::Code(code.clone()), &referral_code);
+
+        events::emit_code_status_changed(
+            &env,
+            events::CodeStatusChangedEvent {
+                code,
+                is_active,
+                changed_by: caller,
+            },
+        );
+    }
+
+    /// Bind a user to a referral code. The caller is either the user or a registered consumer
+    /// acting inside a flow the user already authorized. Attribution is permanent.
+    pub fn attribute(env: Env, caller: Address, user: Address, code: Symbol) {
+        caller.require_auth();
+
+        if caller != user && !is_consumer(&env, &caller) {
+            panic!("Not authorized");
+        }
+        if env.storage().persistent().has(&DataKey::Attribution(user.clone())) {
+            panic!("User already attributed");
+        }
+
+        let mut referral_code = read_code(&env, &code);
+        if !referral_code.is_active {
+            panic!("Code is not active");
+        }
+        if referral_code.owner == user {
+            panic!("Cannot use own code");
+        }
+
+        // Reject attributions that would make the user their own upline
+        let mut upline = referral_code.owner.clone();
+        for _ in 1..MAX_LEVELS {
+            match read_attribution(&env, &upline) {
+                Some(attribution) => {
+                    if attribution.referrer == user {
+                        panic!("Referral cycle");
+                    }
+                    upline = attribution.referrer;
+                }
+                None => break,
+            }
+        }
+
+        let attribution = Attribution {
+            code: code.clone(),
+            referrer: referral_code.owner.clone(),
+            consumer: caller.clone(),
+            attributed_at: env.ledger().timestamp(),
+        };
+
+        referral_code.referred_count += 1;
+
+        env.storage().persistent().set(&DataKey::Attribution(user.clone()), &attribution);
+        env.storage().persistent().set(&DataKey::Code(code.clone()), &referral_code);
+
+        events::emit_referral_attributed(
+            &env,
+            events::ReferralAttributedEvent {
+                user,
+                code,
+                referrer: referral_code.owner,
+                consumer: caller,
+            },
+        );
+    }
+
+    /// Split a referral reward pool across the user's referral chain.
+    ///
+    /// The pool is pulled from the consumer with `transfer`, so the consumer must authorize
+    /// moving `amount` of `token` to this contract. Each level with an active code accrues
+    /// its configured share; whatever is not accrued (short chain, inactive codes, rounding)
+    /// is sent back to the consumer. Returns the amount accrued to referrers.
+    pub fn accrue(env: Env, consumer: Address, user: Address, token: Address, amount: i128) -> i128 {
+        consumer.require_auth();
+
+        if !is_consumer(&env, &consumer) {
+            panic!("Not a registered consumer");
+        }
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let contract = env.current_contract_address();
+        let token_client = token::TokenClient::new(&env, &token);
+        token_client.transfer(&consumer, &contract, &amount);
+
+        let level_shares: Vec<u32> = env.storage().instance().get(&DataKey::LevelShares).unwrap();
+        let chain = referral_chain(&env, &user, level_shares.len());
+
+        let mut accrued: i128 = 0;
+        for (level, referrer) in chain.iter().enumerate() {
+            let level = level as u32;
+            let referrer_code: Symbol = env
+                .storage()
+                .persistent()
+                .get(&DataKey::OwnerCode(referrer.clone()))
+                .unwrap();
+            if !read_code(&env, &referrer_code).is_active {
+                continue;
+            }
+
+            let share = (amount * level_shares.get(level).unwrap() as i128) / BASIS_POINTS as i128;
+            if share == 0 {
+                continue;
+            }
+
+            credit(&env, &referrer, &token, share);
+            accrued += share;
+
+            events::emit_reward_accrued(
+                &env,
+                events::RewardAccruedEvent {
+                    referrer,
+                    user: user.clone(),
+                    token: token.clone(),
+                    level: level + 1,
+                    amount: share,
+                },
+            );
+        }
+
+        let remainder = amount - accrued;
+        if remainder > 0 {
+            token_client.transfer(&contract, &consumer, &remainder);
+        }
+
+        accrued
+    }
+
+    /// Withdraw accrued referral earnings in a given token
+    pub fn withdraw(env: Env, referrer: Address, token: Address, amount: i128) {
+        referrer.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let key = DataKey::Earnings(referrer.clone(), token.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount > balance {
+            panic!("Insufficient earnings");
+        }
+
+        env.storage().persistent().set(&key, &(balance - amount));
+
+        let token_client = token::TokenClient::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &referrer, &amount);
+
+        events::emit_earnings_withdrawn(
+            &env,
+            events::EarningsWithdrawnEvent {
+                referrer,
+                token,
+                amount,
+            },
+        );
+    }
+
+    /// Get referral code details
+    pub fn get_code(env: Env, code: Symbol) -> Option<ReferralCode> {
+        env.storage().persistent().get(&DataKey::Code(code))
+    }
+
+    /// Get the code registered by an owner
+    pub fn get_code_of(env: Env, owner: Address) -> Option<Symbol> {
+        env.storage().persistent().get(&DataKey::OwnerCode(owner))
+    }
+
+    /// Get a user's attribution record
+    pub fn get_attribution(env: Env, user: Address) -> Option<Attribution> {
+        read_attribution(&env, &user)
+    }
+
+    /// Get the direct referrer of a user
+    pub fn get_referrer(env: Env, user: Address) -> Option<Address> {
+        read_attribution(&env, &user).map(|attribution| attribution.referrer)
+    }
+
+    /// Get the user's referral chain, direct referrer first, up to the configured levels
+    pub fn get_referral_chain(env: Env, user: Address) -> Vec<Address> {
+        let level_shares: Vec<u32> = env.storage().instance().get(&DataKey::LevelShares).unwrap();
+        referral_chain(&env, &user, level_shares.len())
+    }
+
+    /// Get the per-level reward shares
+    pub fn get_level_shares(env: Env) -> Vec<u32> {
+        env.storage().instance().get(&DataKey::LevelShares).unwrap()
+    }
+
+    /// Get withdrawable earnings of a referrer in a token
+    pub fn get_earnings(env: Env, referrer: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Earnings(referrer, token))
+            .unwrap_or(0)
+    }
+
+    /// Get lifetime accrued earnings of a referrer in a token
+    pub fn get_total_earned(env: Env, referrer: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalEarned(referrer, token))
+            .unwrap_or(0)
+    }
+
+    /// Check whether an address is a registered consumer
+    pub fn is_consumer(env: Env, consumer: Address) -> bool {
+        is_consumer(&env, &consumer)
+    }
+}
+
+fn read_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+fn read_code(env: &Env, code: &Symbol) -> ReferralCode {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Code(code.clone()))
+        .expect("Code not found")
+}
+
+fn read_attribution(env: &Env, user: &Address) -> Option<Attribution> {
+    env.storage().persistent().get(&DataKey::Attribution(user.clone()))
+}
+
+fn is_consumer(env: &Env, consumer: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Consumer(consumer.clone()))
+        .unwrap_or(false)
+}
+
+fn referral_chain(env: &Env, user: &Address, levels: u32) -> Vec<Address> {
+    let mut chain = Vec::new(env);
+    let mut current = user.clone();
+    while chain.len() < levels {
+        match read_attribution(env, &current) {
+            Some(attribution) => {
+                chain.push_back(attribution.referrer.clone());
+                current = attribution.referrer;
+            }
+            None => break,
+        }
+    }
+    chain
+}
+
+fn credit(env: &Env, referrer: &Address, token: &Address, amount: i128) {
+    let earnings_key = DataKey::Earnings(referrer.clone(), token.clone());
+    let balance: i128 = env.storage().persistent().get(&earnings_key).unwrap_or(0);
+    env.storage().persistent().set(&earnings_key, &(balance + amount));
+
+    let total_key = DataKey::TotalEarned(referrer.clone(), token.clone());
+    let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+    env.storage().persistent().set(&total_key, &(total + amount));
+}
+
+fn validate_level_shares(level_shares: &Vec<u32>) {
+    if level_shares.is_empty() || level_shares.len() > MAX_LEVELS {
+        panic!("Invalid number of levels");
+    }
+
+    let total: u32 = level_shares.iter().sum();
+    if total > BASIS_POINTS {
+        panic!("Level shares exceed 100%");
+    }
+}
@@ -0,0 +1,33 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    LevelShares,
+    Consumer(Address),
+    Code(Symbol),
+    OwnerCode(Address),
+    Attribution(Address),
+    Earnings(Address, Address),    // (Referrer, Token) -> withdrawable balance
+    TotalEarned(Address, Address), // (Referrer, Token) -> lifetime accrued
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ReferralCode {
+    pub code: Symbol,
+    pub owner: Address,
+    pub is_active: bool,
+    pub created_at: u64,
+    pub referred_count: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Attribution {
+    pub code: Symbol,
+    pub referrer: Address,
+    pub consumer: Address,
+    pub attributed_at: u64,
+}
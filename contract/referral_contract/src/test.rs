@@ -0,0 +1,241 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{symbol_short, testutils::Address as _, token, Address, Env, Vec};
+
+fn create_token_contract<'a>(e: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
+    token::StellarAssetClient::new(e, &e.register_stellar_asset_contract_v2(admin.clone()).address())
+}
+
+fn create_referral_contract<'a>(e: &Env, admin: &Address) -> ReferralContractClient<'a> {
+    let contract = ReferralContractClient::new(e, &e.register(ReferralContract, ()));
+    contract.initialize(admin, &Vec::from_array(e, [8000, 2000]));
+    contract
+}
+
+#[test]
+fn test_register_and_attribute() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.register_code(&referrer, &symbol_short!("ALICE"));
+    contract.attribute(&user, &user, &symbol_short!("ALICE"));
+
+    assert_eq!(contract.get_referrer(&user), Some(referrer.clone()));
+    assert_eq!(contract.get_code_of(&referrer), Some(symbol_short!("ALICE")));
+    assert_eq!(contract.get_code(&symbol_short!("ALICE")).unwrap().referred_count, 1);
+}
+
+#[test]
+fn test_multi_level_accrual_and_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let top = Address::generate(&env);
+    let middle = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.add_consumer(&consumer);
+    contract.register_code(&top, &symbol_short!("TOP"));
+    contract.register_code(&middle, &symbol_short!("MID"));
+    contract.attribute(&middle, &middle, &symbol_short!("TOP"));
+    contract.attribute(&consumer, &user, &symbol_short!("MID"));
+
+    assert_eq!(
+        contract.get_referral_chain(&user),
+        Vec::from_array(&env, [middle.clone(), top.clone()])
+    );
+
+    token.mint(&consumer, &1000);
+    let accrued = contract.accrue(&consumer, &user, &token.address, &1000);
+
+    assert_eq!(accrued, 1000);
+    assert_eq!(contract.get_earnings(&middle, &token.address), 800);
+    assert_eq!(contract.get_earnings(&top, &token.address), 200);
+
+    contract.withdraw(&middle, &token.address, &500);
+    assert_eq!(token_client.balance(&middle), 500);
+    assert_eq!(contract.get_earnings(&middle, &token.address), 300);
+    assert_eq!(contract.get_total_earned(&middle, &token.address), 800);
+}
+
+#[test]
+fn test_unused_levels_returned_to_consumer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.add_consumer(&consumer);
+    contract.register_code(&referrer, &symbol_short!("REF"));
+    contract.attribute(&user, &user, &symbol_short!("REF"));
+
+    token.mint(&consumer, &1000);
+    let accrued = contract.accrue(&consumer, &user, &token.address, &1000);
+
+    assert_eq!(accrued, 800);
+    assert_eq!(token_client.balance(&consumer), 200);
+    assert_eq!(token_client.balance(&contract.address), 800);
+}
+
+#[test]
+fn test_inactive_code_skipped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.add_consumer(&consumer);
+    contract.register_code(&referrer, &symbol_short!("REF"));
+    contract.attribute(&user, &user, &symbol_short!("REF"));
+    contract.set_code_active(&admin, &symbol_short!("REF"), &false);
+
+    token.mint(&consumer, &1000);
+    assert_eq!(contract.accrue(&consumer, &user, &token.address, &1000), 0);
+    assert_eq!(contract.get_earnings(&referrer, &token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Referral cycle")]
+fn test_cycle_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.register_code(&alice, &symbol_short!("ALICE"));
+    contract.register_code(&bob, &symbol_short!("BOB"));
+    contract.attribute(&bob, &bob, &symbol_short!("ALICE"));
+    contract.attribute(&alice, &alice, &symbol_short!("BOB"));
+}
+
+#[test]
+#[should_panic(expected = "Not a registered consumer")]
+fn test_accrue_requires_consumer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.accrue(&stranger, &user, &token.address, &1000);
+}
+
+#[test]
+#[should_panic(expected = "Level shares exceed 100%")]
+fn test_invalid_level_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.set_level_shares(&Vec::from_array(&env, [9000, 2000]));
+}
+
+#[test]
+#[should_panic]
+fn test_accrue_pulls_funds_from_consumer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_referral_contract(&env, &admin);
+
+    contract.add_consumer(&consumer);
+    contract.register_code(&referrer, &symbol_short!("REF"));
+    contract.attribute(&user, &user, &symbol_short!("REF"));
+
+    // Funds sitting in the contract for other consumers cannot back this accrual
+    token.mint(&contract.address, &1000);
+    contract.accrue(&consumer, &user, &token.address, &1000);
+}
+
+mod properties {
+    use crate::testutils::{chain_code, create_referral_chain, create_referral_contract};
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+    use test_utils::{create_token, setup_env};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Whatever the chain and shares, an accrual never pays out more than the pool: the
+        /// accrued part stays in the contract as earnings and the rest goes back to the consumer.
+        #[test]
+        fn prop_accrue_conserves_pool(
+            level_shares in proptest::collection::vec(0u32..3334, 1..4),
+            depth in 0usize..5,
+            inactive in proptest::collection::vec(any::<bool>(), 4),
+            amount in 1i128..1_000_000_000_000,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            let admin = Address::generate(&env);
+            let consumer = Address::generate(&env);
+            let client = create_referral_contract(&env, &admin, &level_shares);
+            client.add_consumer(&consumer);
+
+            let (user, chain) = create_referral_chain(&env, &client, depth);
+            for (level, referrer) in chain.iter().enumerate() {
+                if inactive[level] {
+                    client.set_code_active(referrer, &chain_code(&env, level), &false);
+                }
+            }
+
+            token.mint(&consumer, amount);
+            let accrued = client.accrue(&consumer, &user, &token.address, &amount);
+
+            let earned: i128 = chain.iter().map(|referrer| client.get_earnings(referrer, &token.address)).sum();
+            prop_assert!(accrued >= 0 && accrued <= amount);
+            prop_assert_eq!(earned, accrued);
+            prop_assert_eq!(token.balance(&client.address), accrued);
+            prop_assert_eq!(token.balance(&consumer), amount - accrued);
+
+            for referrer in chain.iter() {
+                let earnings = client.get_earnings(referrer, &token.address);
+                if earnings > 0 {
+                    client.withdraw(referrer, &token.address, &earnings);
+                }
+            }
+            prop_assert_eq!(token.balance(&client.address), 0);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, Vec};
+
+use crate::{ReferralContract, ReferralContractClient};
+
+/// Register and initialize a referral contract with the given per-level shares
+pub fn create_referral_contract<'a>(env: &Env, admin: &Address, level_shares: &[u32]) -> ReferralContractClient<'a> {
+    let client = ReferralContractClient::new(env, &env.register(ReferralContract, ()));
+    client.initialize(admin, &Vec::from_slice(env, level_shares));
+    client
+}
+
+/// Code registered by the `level`-th referrer of a chain built by `create_referral_chain`
+pub fn chain_code(env: &Env, level: usize) -> Symbol {
+    Symbol::new(env, &std::format!("LEVEL{}", level))
+}
+
+/// Attribute a fresh user to a chain of `depth` fresh referrers, each attributed to the
+/// one above it. Returns the user and the chain, direct referrer first. Requires mocked auths.
+pub fn create_referral_chain(
+    env: &Env,
+    client: &ReferralContractClient,
+    depth: usize,
+) -> (Address, std::vec::Vec<Address>) {
+    let chain: std::vec::Vec<Address> = (0..depth).map(|_| Address::generate(env)).collect();
+    for (level, referrer) in chain.iter().enumerate() {
+        client.register_code(referrer, &chain_code(env, level));
+    }
+    for level in (1..depth).rev() {
+        let referrer = &chain[level - 1];
+        client.attribute(referrer, referrer, &chain_code(env, level));
+    }
+
+    let user = Address::generate(env);
+    if depth > 0 {
+        client.attribute(&user, &user, &chain_code(env, 0));
+    }
+
+    (user, chain)
+}
@@ -0,0 +1,30 @@
+use soroban_sdk::{contracttype, Env, Symbol};
+
+use crate::storage_types::ParamValue;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ParamSetEvent {
+    pub namespace: Symbol,
+    pub key: Symbol,
+    pub value: ParamValue,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ParamRemovedEvent {
+    pub namespace: Symbol,
+    pub key: Symbol,
+}
+
+#[allow(deprecated)]
+pub fn emit_param_set(env: &Env, event: ParamSetEvent) {
+    env.events()
+        .publish((Symbol::new(env, "param_set"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_param_removed(env: &Env, event: ParamRemovedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "param_removed"),), event);
+}
@@ -0,0 +1,133 @@
+#![no_std]
+
+mod events;
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Vec};
+
+pub use storage_types::*;
+
+#[contract]
+pub struct RegistryContract;
+
+#[contractimpl]
+impl RegistryContract {
+    /// Initialize the registry; only `governance` can write parameters
+    pub fn initialize(env: Env, governance: Address) {
+        if env.storage().instance().has(&DataKey::Governance) {
+            panic!("Already initialized");
+        }
+        governance.require_auth();
+
+        env.storage().instance().set(&DataKey::Governance, &governance);
+    }
+
+    /// Set a parameter. An existing parameter keeps its type.
+    pub fn set_param(env: Env, namespace: Symbol, key: Symbol, value: ParamValue) {
+        read_governance(&env).require_auth();
+
+        let param_key = DataKey::Param(namespace.clone(), key.clone());
+        if let Some(existing) = env.storage().persistent().get::<DataKey, ParamValue>(&param_key) {
+            if !same_type(&existing, &value) {
+                panic!("Parameter type mismatch");
+            }
+        } else {
+            let mut keys = read_keys(&env, &namespace);
+            keys.push_back(key.clone());
+            env.storage().persistent().set(&DataKey::Keys(namespace.clone()), &keys);
+        }
+
+        env.storage().persistent().set(&param_key, &value);
+
+        events::emit_param_set(&env, events::ParamSetEvent { namespace, key, value });
+    }
+
+    /// Remove a parameter so readers fall back to their defaults
+    pub fn remove_param(env: Env, namespace: Symbol, key: Symbol) {
+        read_governance(&env).require_auth();
+
+        let mut keys = read_keys(&env, &namespace);
+        let index = keys.first_index_of(&key).expect("Parameter not found");
+        keys.remove(index);
+
+        env.storage().persistent().set(&DataKey::Keys(namespace.clone()), &keys);
+        env.storage().persistent().remove(&DataKey::Param(namespace.clone(), key.clone()));
+
+        events::emit_param_removed(&env, events::ParamRemovedEvent { namespace, key });
+    }
+
+    /// Hand write access to a new governance contract
+    pub fn set_governance(env: Env, new_governance: Address) {
+        read_governance(&env).require_auth();
+        env.storage().instance().set(&DataKey::Governance, &new_governance);
+    }
+
+    /// Get a parameter of any type
+    pub fn get_param(env: Env, namespace: Symbol, key: Symbol) -> Option<ParamValue> {
+        env.storage().persistent().get(&DataKey::Param(namespace, key))
+    }
+
+    /// Get a u32 parameter
+    pub fn get_u32(env: Env, namespace: Symbol, key: Symbol) -> Option<u32> {
+        match Self::get_param(env, namespace, key) {
+            Some(ParamValue::U32(value)) => Some(value),
+            None => None,
+            _ => panic!("Parameter type mismatch"),
+        }
+    }
+
+    /// Get a u64 parameter
+    pub fn get_u64(env: Env, namespace: Symbol, key: Symbol) -> Option<u64> {
+        match Self::get_param(env, namespace, key) {
+            Some(ParamValue::U64(value)) => Some(value),
+            None => None,
+            _ => panic!("Parameter type mismatch"),
+        }
+    }
+
+    /// Get an i128 parameter
+    pub fn get_i128(env: Env, namespace: Symbol, key: Symbol) -> Option<i128> {
+        match Self::get_param(env, namespace, key) {
+            Some(ParamValue::I128(value)) => Some(value),
+            None => None,
+            _ => panic!("Parameter type mismatch"),
+        }
+    }
+
+    /// List the keys set in a namespace
+    pub fn get_keys(env: Env, namespace: Symbol) -> Vec<Symbol> {
+        read_keys(&env, &namespace)
+    }
+
+    /// Get the governance contract allowed to write parameters
+    pub fn get_governance(env: Env) -> Address {
+        read_governance(&env)
+    }
+}
+
+fn read_governance(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Governance).unwrap()
+}
+
+fn read_keys(env: &Env, namespace: &Symbol) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Keys(namespace.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn same_type(a: &ParamValue, b: &ParamValue) -> bool {
+    matches!(
+        (a, b),
+        (ParamValue::U32(_), ParamValue::U32(_))
+            | (ParamValue::U64(_), ParamValue::U64(_))
+            | (ParamValue::I128(_), ParamValue::I128(_))
+            | (ParamValue::Bool(_), ParamValue::Bool(_))
+            | (ParamValue::Address(_), ParamValue::Address(_))
+    )
+}
@@ -0,0 +1,19 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Governance,
+    Param(Symbol, Symbol), // (Namespace, Key) -> ParamValue
+    Keys(Symbol),          // Namespace -> keys set in it
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[contracttype]
+pub enum ParamValue {
+    U32(u32),
+    U64(u64),
+    I128(i128),
+    Bool(bool),
+    Address(Address),
+}
@@ -0,0 +1,137 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal,
+};
+
+fn create_registry_contract<'a>(e: &Env, governance: &Address) -> RegistryContractClient<'a> {
+    let contract = RegistryContractClient::new(e, &e.register(RegistryContract, ()));
+    contract.initialize(governance);
+    contract
+}
+
+#[test]
+fn test_set_and_read_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let governance = Address::generate(&env);
+    let registry = create_registry_contract(&env, &governance);
+    let ns = symbol_short!("subs");
+
+    registry.set_param(&ns, &symbol_short!("grace"), &ParamValue::U32(3));
+    registry.set_param(&ns, &symbol_short!("fee_bps"), &ParamValue::I128(250));
+    registry.set_param(&ns, &symbol_short!("grace"), &ParamValue::U32(5));
+
+    assert_eq!(registry.get_u32(&ns, &symbol_short!("grace")), Some(5));
+    assert_eq!(registry.get_i128(&ns, &symbol_short!("fee_bps")), Some(250));
+    assert_eq!(registry.get_u32(&ns, &symbol_short!("missing")), None);
+    assert_eq!(registry.get_keys(&ns).len(), 2);
+
+    registry.remove_param(&ns, &symbol_short!("grace"));
+    assert_eq!(registry.get_param(&ns, &symbol_short!("grace")), None);
+    assert_eq!(registry.get_keys(&ns).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Parameter type mismatch")]
+fn test_type_is_fixed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let governance = Address::generate(&env);
+    let registry = create_registry_contract(&env, &governance);
+    let ns = symbol_short!("subs");
+
+    registry.set_param(&ns, &symbol_short!("grace"), &ParamValue::U32(3));
+    registry.set_param(&ns, &symbol_short!("grace"), &ParamValue::Bool(true));
+}
+
+#[test]
+fn test_initialize_requires_governance_auth() {
+    let env = Env::default();
+
+    let governance = Address::generate(&env);
+    let registry = RegistryContractClient::new(&env, &env.register(RegistryContract, ()));
+
+    assert!(registry.try_initialize(&governance).is_err());
+
+    env.mock_all_auths();
+    registry.initialize(&governance);
+    assert_eq!(env.auths()[0].0, governance);
+}
+
+#[test]
+#[should_panic]
+fn test_only_governance_writes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let governance = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let registry = create_registry_contract(&env, &governance);
+    let args = (symbol_short!("subs"), symbol_short!("grace"), ParamValue::U32(3));
+
+    env.mock_auths(&[MockAuth {
+        address: &stranger,
+        invoke: &MockAuthInvoke {
+            contract: &registry.address,
+            fn_name: "set_param",
+            args: args.clone().into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    registry.set_param(&args.0, &args.1, &args.2);
+}
+
+mod properties {
+    extern crate std;
+
+    use crate::testutils::{create_registry_contract, param_key, param_value};
+    use core::mem::discriminant;
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{symbol_short, Address};
+    use std::collections::BTreeMap;
+    use test_utils::setup_env;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Against a plain map model: a parameter never changes type, removals only succeed for
+        /// set keys, and the key index lists each set parameter exactly once.
+        #[test]
+        fn prop_registry_matches_model(
+            ops in proptest::collection::vec((any::<bool>(), 0u32..5, 0u8..4, any::<u32>()), 1..24),
+        ) {
+            let env = setup_env();
+            let registry = create_registry_contract(&env, &Address::generate(&env));
+            let ns = symbol_short!("props");
+            let mut model = BTreeMap::new();
+
+            for (set, n, kind, raw) in ops.iter() {
+                let key = param_key(&env, *n);
+                if *set {
+                    let value = param_value(*kind, *raw);
+                    let allowed = model.get(n).is_none_or(|existing| discriminant(existing) == discriminant(&value));
+                    prop_assert_eq!(registry.try_set_param(&ns, &key, &value).is_ok(), allowed);
+                    if allowed {
+                        model.insert(*n, value);
+                    }
+                } else {
+                    prop_assert_eq!(registry.try_remove_param(&ns, &key).is_ok(), model.remove(n).is_some());
+                }
+            }
+
+            let keys = registry.get_keys(&ns);
+            prop_assert_eq!(keys.len() as usize, model.len());
+            for (n, value) in model.iter() {
+                prop_assert!(keys.contains(param_key(&env, *n)));
+                prop_assert_eq!(registry.get_param(&ns, &param_key(&env, *n)), Some(value.clone()));
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+extern crate std;
+
+use soroban_sdk::{Address, Env, Symbol};
+
+use crate::{ParamValue, RegistryContract, RegistryContractClient};
+
+/// Register and initialize a registry governed by `governance`
+pub fn create_registry_contract<'a>(env: &Env, governance: &Address) -> RegistryContractClient<'a> {
+    let client = RegistryContractClient::new(env, &env.register(RegistryContract, ()));
+    client.initialize(governance);
+    client
+}
+
+/// Parameter key `n` of a generated namespace
+pub fn param_key(env: &Env, n: u32) -> Symbol {
+    Symbol::new(env, &std::format!("key{}", n))
+}
+
+/// A parameter of the `kind`-th type (u32, u64, i128, bool, in that order) built from `raw`
+pub fn param_value(kind: u8, raw: u32) -> ParamValue {
+    match kind % 4 {
+        0 => ParamValue::U32(raw),
+        1 => ParamValue::U64(raw as u64),
+        2 => ParamValue::I128(raw as i128),
+        _ => ParamValue::Bool(raw & 1 == 0),
+    }
+}
@@ -0,0 +1,140 @@
+#![no_std]
+
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol};
+
+pub use storage_types::*;
+
+/// Loyalty points. Balances are non-transferable: points can only be minted by protocol
+/// contracts when users earn them and burned by protocol contracts when users redeem them.
+#[contract]
+pub struct RewardsTokenContract;
+
+#[contractimpl]
+impl RewardsTokenContract {
+    /// Initialize the token with admin and display metadata
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+    }
+
+    /// Allow a protocol contract to mint and burn points
+    pub fn add_minter(env: Env, minter: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().set(&DataKey::Minter(minter), &true);
+    }
+
+    /// Revoke a protocol contract's mint and burn rights
+    pub fn remove_minter(env: Env, minter: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().remove(&DataKey::Minter(minter));
+    }
+
+    /// Mint points to a user
+    pub fn mint(env: Env, minter: Address, to: Address, amount: i128) {
+        minter.require_auth();
+        require_minter(&env, &minter);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let balance = read_balance(&env, &to);
+        env.storage().persistent().set(&DataKey::Balance(to.clone()), &(balance + amount));
+
+        let supply: i128 = env.storage().instance().get(&DataKey::TotalSupply).unwrap();
+        env.storage().instance().set(&DataKey::TotalSupply, &(supply + amount));
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((Symbol::new(&env, "mint"), minter, to), amount);
+    }
+
+    /// Burn a user's points as part of a redemption; requires both the minter and the user
+    pub fn burn(env: Env, minter: Address, from: Address, amount: i128) {
+        minter.require_auth();
+        from.require_auth();
+        require_minter(&env, &minter);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let balance = read_balance(&env, &from);
+        if amount > balance {
+            panic!("Insufficient balance");
+        }
+        env.storage().persistent().set(&DataKey::Balance(from.clone()), &(balance - amount));
+
+        let supply: i128 = env.storage().instance().get(&DataKey::TotalSupply).unwrap();
+        env.storage().instance().set(&DataKey::TotalSupply, &(supply - amount));
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((Symbol::new(&env, "burn"), minter, from), amount);
+    }
+
+    /// Get a user's points balance
+    pub fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+
+    /// Get the total points outstanding
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::TotalSupply).unwrap()
+    }
+
+    /// Check whether an address may mint and burn
+    pub fn is_minter(env: Env, minter: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Minter(minter))
+            .unwrap_or(false)
+    }
+
+    pub fn name(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Name).unwrap()
+    }
+
+    pub fn symbol(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Symbol).unwrap()
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        0
+    }
+}
+
+fn read_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+fn read_balance(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(id.clone()))
+        .unwrap_or(0)
+}
+
+fn require_minter(env: &Env, minter: &Address) {
+    let is_minter: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Minter(minter.clone()))
+        .unwrap_or(false);
+    if !is_minter {
+        panic!("Not a minter");
+    }
+}
@@ -0,0 +1,12 @@
+use soroban_sdk::{contracttype, Address};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Name,
+    Symbol,
+    TotalSupply,
+    Minter(Address),
+    Balance(Address),
+}
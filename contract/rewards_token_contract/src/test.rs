@@ -0,0 +1,80 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+fn create_rewards_contract<'a>(e: &Env, admin: &Address) -> RewardsTokenContractClient<'a> {
+    let contract = RewardsTokenContractClient::new(e, &e.register(RewardsTokenContract, ()));
+    contract.initialize(
+        admin,
+        &String::from_str(e, "Gatherraa Points"),
+        &String::from_str(e, "GPT"),
+    );
+    contract
+}
+
+#[test]
+fn test_mint_and_burn() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract = create_rewards_contract(&env, &admin);
+
+    contract.add_minter(&minter);
+    contract.mint(&minter, &user, &100);
+    assert_eq!(contract.balance(&user), 100);
+    assert_eq!(contract.total_supply(), 100);
+
+    contract.burn(&minter, &user, &40);
+    assert_eq!(contract.balance(&user), 60);
+    assert_eq!(contract.total_supply(), 60);
+}
+
+#[test]
+#[should_panic(expected = "Not a minter")]
+fn test_mint_requires_minter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let contract = create_rewards_contract(&env, &admin);
+
+    contract.mint(&stranger, &stranger, &100);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient balance")]
+fn test_burn_more_than_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract = create_rewards_contract(&env, &admin);
+
+    contract.add_minter(&minter);
+    contract.mint(&minter, &user, &10);
+    contract.burn(&minter, &user, &11);
+}
+
+#[test]
+fn test_removed_minter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract = create_rewards_contract(&env, &admin);
+
+    contract.add_minter(&minter);
+    contract.remove_minter(&minter);
+
+    assert!(!contract.is_minter(&minter));
+    assert!(contract.try_mint(&minter, &user, &10).is_err());
+}
@@ -0,0 +1,44 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub struct DepositRegisteredEvent {
+    pub reference: BytesN<32>,
+    pub anchor: Address,
+    pub payer: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct DepositSettledEvent {
+    pub reference: BytesN<32>,
+    pub anchor: Address,
+    pub payer: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct DepositCancelledEvent {
+    pub reference: BytesN<32>,
+    pub anchor: Address,
+}
+
+#[allow(deprecated)]
+pub fn emit_deposit_registered(env: &Env, event: DepositRegisteredEvent) {
+    env.events()
+        .publish((Symbol::new(env, "deposit_registered"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_deposit_settled(env: &Env, event: DepositSettledEvent) {
+    env.events()
+        .publish((Symbol::new(env, "deposit_settled"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_deposit_cancelled(env: &Env, event: DepositCancelledEvent) {
+    env.events()
+        .publish((Symbol::new(env, "deposit_cancelled"),), event);
+}
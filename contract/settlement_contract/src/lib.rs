@@ -0,0 +1,223 @@
+#![no_std]
+
+mod events;
+mod storage_types;
+mod targets;
+
+#[cfg(test)]
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, IntoVal};
+
+pub use storage_types::*;
+
+#[contract]
+pub struct SettlementContract;
+
+#[contractimpl]
+impl SettlementContract {
+    /// Initialize the contract with the admin managing the anchor registry
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    /// Register a fiat on-ramp anchor
+    pub fn add_anchor(env: Env, anchor: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().set(&DataKey::Anchor(anchor), &true);
+    }
+
+    /// Remove an anchor; pending deposits can no longer be confirmed by it
+    pub fn remove_anchor(env: Env, anchor: Address) {
+        read_admin(&env).require_auth();
+        env.storage().persistent().remove(&DataKey::Anchor(anchor));
+    }
+
+    /// Top up the anchor's on-chain liquidity used to settle confirmed deposits
+    pub fn deposit_float(env: Env, anchor: Address, token: Address, amount: i128) {
+        anchor.require_auth();
+        require_anchor(&env, &anchor);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let token_client = token::TokenClient::new(&env, &token);
+        token_client.transfer(&anchor, env.current_contract_address(), &amount);
+
+        let float = read_float(&env, &anchor, &token);
+        write_float(&env, &anchor, &token, float + amount);
+    }
+
+    /// Withdraw unused liquidity; allowed even after the anchor is removed
+    pub fn withdraw_float(env: Env, anchor: Address, token: Address, amount: i128) {
+        anchor.require_auth();
+
+        let float = read_float(&env, &anchor, &token);
+        if amount <= 0 || amount > float {
+            panic!("Invalid amount");
+        }
+
+        write_float(&env, &anchor, &token, float - amount);
+
+        let token_client = token::TokenClient::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &anchor, &amount);
+    }
+
+    /// Register an off-chain payment reference awaiting confirmation
+    pub fn register_deposit(
+        env: Env,
+        anchor: Address,
+        reference: BytesN<32>,
+        payer: Address,
+        token: Address,
+        amount: i128,
+        target: SettlementTarget,
+    ) {
+        anchor.require_auth();
+        require_anchor(&env, &anchor);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        if env.storage().persistent().has(&DataKey::Deposit(reference.clone())) {
+            panic!("Reference already registered");
+        }
+
+        let deposit = Deposit {
+            reference: reference.clone(),
+            anchor: anchor.clone(),
+            payer: payer.clone(),
+            token,
+            amount,
+            target,
+            status: DepositStatus::Pending,
+            registered_at: env.ledger().timestamp(),
+            settled_at: 0,
+            gift_id: None,
+        };
+
+        env.storage().persistent().set(&DataKey::Deposit(reference.clone()), &deposit);
+
+        events::emit_deposit_registered(
+            &env,
+            events::DepositRegisteredEvent {
+                reference,
+                anchor,
+                payer,
+                amount,
+            },
+        );
+    }
+
+    /// Confirm an off-chain payment and fund its target from the anchor's float.
+    ///
+    /// The anchor's signature covers the reference and amount, so a relayer can submit the
+    /// attestation on the anchor's behalf without being able to alter it.
+    pub fn confirm_deposit(env: Env, reference: BytesN<32>) {
+        let mut deposit = read_deposit(&env, &reference);
+        deposit
+            .anchor
+            .require_auth_for_args((reference.clone(), deposit.amount).into_val(&env));
+        require_anchor(&env, &deposit.anchor);
+
+        if deposit.status != DepositStatus::Pending {
+            panic!("Deposit not pending");
+        }
+
+        let float = read_float(&env, &deposit.anchor, &deposit.token);
+        if deposit.amount > float {
+            panic!("Insufficient anchor float");
+        }
+        write_float(&env, &deposit.anchor, &deposit.token, float - deposit.amount);
+
+        deposit.status = DepositStatus::Settled;
+        deposit.settled_at = env.ledger().timestamp();
+        deposit.gift_id = targets::settle(&env, &deposit);
+
+        env.storage().persistent().set(&DataKey::Deposit(reference.clone()), &deposit);
+
+        events::emit_deposit_settled(
+            &env,
+            events::DepositSettledEvent {
+                reference,
+                anchor: deposit.anchor,
+                payer: deposit.payer,
+                amount: deposit.amount,
+            },
+        );
+    }
+
+    /// Cancel a pending deposit whose off-chain payment failed
+    pub fn cancel_deposit(env: Env, reference: BytesN<32>) {
+        let mut deposit = read_deposit(&env, &reference);
+        deposit.anchor.require_auth();
+
+        if deposit.status != DepositStatus::Pending {
+            panic!("Deposit not pending");
+        }
+
+        deposit.status = DepositStatus::Cancelled;
+        env.storage().persistent().set(&DataKey::Deposit(reference.clone()), &deposit);
+
+        events::emit_deposit_cancelled(
+            &env,
+            events::DepositCancelledEvent {
+                reference,
+                anchor: deposit.anchor,
+            },
+        );
+    }
+
+    /// Get deposit details
+    pub fn get_deposit(env: Env, reference: BytesN<32>) -> Option<Deposit> {
+        env.storage().persistent().get(&DataKey::Deposit(reference))
+    }
+
+    /// Get an anchor's available liquidity in a token
+    pub fn get_float(env: Env, anchor: Address, token: Address) -> i128 {
+        read_float(&env, &anchor, &token)
+    }
+
+    /// Check whether an address is a registered anchor
+    pub fn is_anchor(env: Env, anchor: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Anchor(anchor))
+    }
+}
+
+fn read_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+fn require_anchor(env: &Env, anchor: &Address) {
+    if !env.storage().persistent().has(&DataKey::Anchor(anchor.clone())) {
+        panic!("Not a registered anchor");
+    }
+}
+
+fn read_deposit(env: &Env, reference: &BytesN<32>) -> Deposit {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Deposit(reference.clone()))
+        .expect("Deposit not found")
+}
+
+fn read_float(env: &Env, anchor: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Float(anchor.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+fn write_float(env: &Env, anchor: &Address, token: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Float(anchor.clone(), token.clone()), &amount);
+}
@@ -0,0 +1,42 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Anchor(Address),
+    Float(Address, Address), // (Anchor, Token) -> liquidity available for settlements
+    Deposit(BytesN<32>),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum SettlementTarget {
+    /// Credit the payer's wallet directly
+    Wallet,
+    /// Buy a plan on a subscription contract for the payer (delivered as a claimable gift)
+    Subscription(Address, u32),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[contracttype]
+pub enum DepositStatus {
+    Pending,
+    Settled,
+    Cancelled,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Deposit {
+    pub reference: BytesN<32>,
+    pub anchor: Address,
+    pub payer: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub target: SettlementTarget,
+    pub status: DepositStatus,
+    pub registered_at: u64,
+    pub settled_at: u64,
+    pub gift_id: Option<u64>,
+}
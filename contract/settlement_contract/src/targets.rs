@@ -0,0 +1,59 @@
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contractclient, token, vec, Address, Env, IntoVal, String, Symbol, Vec,
+};
+
+use crate::storage_types::*;
+
+/// Subset of the subscription contract interface used for settlement
+#[allow(dead_code)]
+#[contractclient(name = "SubscriptionClient")]
+pub trait SubscriptionInterface {
+    fn gift_subscription(
+        env: Env,
+        from: Address,
+        to: Address,
+        plan_id: u32,
+        message: String,
+        deliverable_at: u64,
+    ) -> u64;
+    fn get_payment_recipient(env: Env) -> Address;
+}
+
+/// Move a confirmed deposit's funds into its target. Returns the gift id for subscription targets.
+pub fn settle(env: &Env, deposit: &Deposit) -> Option<u64> {
+    match &deposit.target {
+        SettlementTarget::Wallet => {
+            let token_client = token::TokenClient::new(env, &deposit.token);
+            token_client.transfer(&env.current_contract_address(), &deposit.payer, &deposit.amount);
+            None
+        }
+        SettlementTarget::Subscription(contract, plan_id) => {
+            let subscription = SubscriptionClient::new(env, contract);
+            let recipient = subscription.get_payment_recipient();
+
+            // The subscription contract pulls the plan price from this contract, so pre-authorize
+            // exactly that transfer. A price that differs from the deposit amount fails the call.
+            env.authorize_as_current_contract(vec![
+                env,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: deposit.token.clone(),
+                        fn_name: Symbol::new(env, "transfer"),
+                        args: (env.current_contract_address(), recipient, deposit.amount)
+                            .into_val(env),
+                    },
+                    sub_invocations: Vec::new(env),
+                }),
+            ]);
+
+            Some(subscription.gift_subscription(
+                &env.current_contract_address(),
+                &deposit.payer,
+                plan_id,
+                &String::from_str(env, ""),
+                &0,
+            ))
+        }
+    }
+}
@@ -0,0 +1,263 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    token, Address, BytesN, Env, IntoVal, String,
+};
+use subscription_contract::testutils::{create_simple_plan, create_subscription_contract};
+
+/// Stand-in for the subscription contract's gifting entrypoints
+#[contract]
+pub struct MockSubscription;
+
+#[contractimpl]
+impl MockSubscription {
+    pub fn init(env: Env, token: Address, recipient: Address, price: i128) {
+        env.storage().instance().set(&symbol_short!("token"), &token);
+        env.storage().instance().set(&symbol_short!("recip"), &recipient);
+        env.storage().instance().set(&symbol_short!("price"), &price);
+    }
+
+    pub fn gift_subscription(
+        env: Env,
+        from: Address,
+        to: Address,
+        _plan_id: u32,
+        _message: String,
+        _deliverable_at: u64,
+    ) -> u64 {
+        from.require_auth();
+        let token: Address = env.storage().instance().get(&symbol_short!("token")).unwrap();
+        let recipient: Address = env.storage().instance().get(&symbol_short!("recip")).unwrap();
+        let price: i128 = env.storage().instance().get(&symbol_short!("price")).unwrap();
+        token::TokenClient::new(&env, &token).transfer(&from, &recipient, &price);
+        env.storage().persistent().set(&to, &true);
+        7
+    }
+
+    pub fn get_payment_recipient(env: Env) -> Address {
+        env.storage().instance().get(&symbol_short!("recip")).unwrap()
+    }
+
+    pub fn has_gift(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&user)
+    }
+}
+
+fn create_token_contract<'a>(e: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
+    token::StellarAssetClient::new(e, &e.register_stellar_asset_contract_v2(admin.clone()).address())
+}
+
+fn setup<'a>(env: &Env) -> (SettlementContractClient<'a>, token::StellarAssetClient<'a>, Address) {
+    let admin = Address::generate(env);
+    let anchor = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = create_token_contract(env, &token_admin);
+    let contract = SettlementContractClient::new(env, &env.register(SettlementContract, ()));
+
+    contract.initialize(&admin);
+    contract.add_anchor(&anchor);
+    token.mint(&anchor, &10000);
+    contract.deposit_float(&anchor, &token.address, &5000);
+
+    (contract, token, anchor)
+}
+
+#[test]
+fn test_wallet_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, anchor) = setup(&env);
+    let token_client = token::Client::new(&env, &token.address);
+    let payer = Address::generate(&env);
+    let reference = BytesN::from_array(&env, &[1; 32]);
+
+    contract.register_deposit(&anchor, &reference, &payer, &token.address, &1200, &SettlementTarget::Wallet);
+    contract.confirm_deposit(&reference);
+
+    assert_eq!(token_client.balance(&payer), 1200);
+    assert_eq!(contract.get_float(&anchor, &token.address), 3800);
+    assert_eq!(contract.get_deposit(&reference).unwrap().status, DepositStatus::Settled);
+}
+
+#[test]
+fn test_subscription_settlement_with_attestation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, anchor) = setup(&env);
+    let token_client = token::Client::new(&env, &token.address);
+    let payer = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let subscription = MockSubscriptionClient::new(&env, &env.register(MockSubscription, ()));
+    subscription.init(&token.address, &treasury, &1000);
+
+    let reference = BytesN::from_array(&env, &[2; 32]);
+    contract.register_deposit(
+        &anchor,
+        &reference,
+        &payer,
+        &token.address,
+        &1000,
+        &SettlementTarget::Subscription(subscription.address.clone(), 1),
+    );
+
+    // Only the anchor's attestation over (reference, amount) is provided; the contract
+    // authorizes its own payment to the subscription contract.
+    env.mock_auths(&[MockAuth {
+        address: &anchor,
+        invoke: &MockAuthInvoke {
+            contract: &contract.address,
+            fn_name: "confirm_deposit",
+            args: (reference.clone(), 1000i128).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    contract.confirm_deposit(&reference);
+
+    assert!(subscription.has_gift(&payer));
+    assert_eq!(token_client.balance(&treasury), 1000);
+    assert_eq!(contract.get_deposit(&reference).unwrap().gift_id, Some(7));
+}
+
+#[test]
+fn test_same_ledger_settlements_get_separate_gifts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, anchor) = setup(&env);
+    let first_payer = Address::generate(&env);
+    let second_payer = Address::generate(&env);
+    let subscription = create_subscription_contract(&env, &token.address);
+    let plan_id = create_simple_plan(&env, &subscription, 1000, 30);
+
+    let target = SettlementTarget::Subscription(subscription.address.clone(), plan_id);
+    let first = BytesN::from_array(&env, &[3; 32]);
+    let second = BytesN::from_array(&env, &[4; 32]);
+    contract.register_deposit(&anchor, &first, &first_payer, &token.address, &1000, &target);
+    contract.register_deposit(&anchor, &second, &second_payer, &token.address, &1000, &target);
+
+    // Both confirmations land in the same ledger
+    contract.confirm_deposit(&first);
+    contract.confirm_deposit(&second);
+
+    let first_gift = contract.get_deposit(&first).unwrap().gift_id.unwrap();
+    let second_gift = contract.get_deposit(&second).unwrap().gift_id.unwrap();
+    assert_ne!(first_gift, second_gift);
+
+    subscription.claim_gift(&first_payer, &first_gift);
+    subscription.claim_gift(&second_payer, &second_gift);
+    assert_eq!(subscription.get_subscription(&first_payer).unwrap().plan_id, plan_id);
+    assert_eq!(subscription.get_subscription(&second_payer).unwrap().plan_id, plan_id);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient anchor float")]
+fn test_confirm_requires_float() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, anchor) = setup(&env);
+    let payer = Address::generate(&env);
+    let reference = BytesN::from_array(&env, &[3; 32]);
+
+    contract.register_deposit(&anchor, &reference, &payer, &token.address, &6000, &SettlementTarget::Wallet);
+    contract.confirm_deposit(&reference);
+}
+
+#[test]
+#[should_panic(expected = "Deposit not pending")]
+fn test_cancelled_deposit_cannot_settle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, anchor) = setup(&env);
+    let payer = Address::generate(&env);
+    let reference = BytesN::from_array(&env, &[4; 32]);
+
+    contract.register_deposit(&anchor, &reference, &payer, &token.address, &100, &SettlementTarget::Wallet);
+    contract.cancel_deposit(&reference);
+    contract.confirm_deposit(&reference);
+}
+
+#[test]
+#[should_panic(expected = "Not a registered anchor")]
+fn test_unregistered_anchor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _anchor) = setup(&env);
+    let stranger = Address::generate(&env);
+    let payer = Address::generate(&env);
+
+    contract.register_deposit(
+        &stranger,
+        &BytesN::from_array(&env, &[5; 32]),
+        &payer,
+        &token.address,
+        &100,
+        &SettlementTarget::Wallet,
+    );
+}
+
+mod properties {
+    use crate::testutils::{create_funded_anchor, create_settlement_contract, reference};
+    use crate::{DepositStatus, SettlementTarget};
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+    use test_utils::{create_token, setup_env};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Confirmations only ever spend the anchor's float: the contract's balance always
+        /// matches the float left, and payers receive exactly the deposits that settled.
+        #[test]
+        fn prop_settlements_never_exceed_float(
+            float in 0i128..100_000,
+            deposits in proptest::collection::vec((1i128..50_000, 0u8..3), 1..8),
+            withdraw in 0i128..100_000,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            let client = create_settlement_contract(&env, &Address::generate(&env));
+            let anchor = create_funded_anchor(&env, &client, &token.address, float);
+            let payer = Address::generate(&env);
+
+            let mut settled = 0;
+            for (n, (amount, action)) in deposits.iter().enumerate() {
+                let reference = reference(&env, n as u32);
+                client.register_deposit(&anchor, &reference, &payer, &token.address, amount, &SettlementTarget::Wallet);
+
+                match action {
+                    0 => {
+                        let available = client.get_float(&anchor, &token.address);
+                        let confirmed = client.try_confirm_deposit(&reference).is_ok();
+                        prop_assert_eq!(confirmed, *amount <= available);
+                        if confirmed {
+                            settled += amount;
+                            prop_assert_eq!(client.get_deposit(&reference).unwrap().status, DepositStatus::Settled);
+                        }
+                    }
+                    1 => client.cancel_deposit(&reference),
+                    _ => {}
+                }
+
+                prop_assert_eq!(token.balance(&payer), settled);
+                prop_assert_eq!(token.balance(&client.address), client.get_float(&anchor, &token.address));
+            }
+
+            let remaining = client.get_float(&anchor, &token.address);
+            prop_assert_eq!(remaining, float - settled);
+            if withdraw > 0 && withdraw <= remaining {
+                client.withdraw_float(&anchor, &token.address, &withdraw);
+                prop_assert_eq!(token.balance(&anchor), withdraw);
+            }
+            prop_assert_eq!(token.balance(&client.address), client.get_float(&anchor, &token.address));
+        }
+    }
+}
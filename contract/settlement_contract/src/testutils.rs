@@ -0,0 +1,30 @@
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env};
+
+use crate::{SettlementContract, SettlementContractClient};
+
+/// Register and initialize a settlement contract
+pub fn create_settlement_contract<'a>(env: &Env, admin: &Address) -> SettlementContractClient<'a> {
+    let client = SettlementContractClient::new(env, &env.register(SettlementContract, ()));
+    client.initialize(admin);
+    client
+}
+
+/// Register a fresh anchor and deposit `float` of `token` as its liquidity. Requires mocked auths.
+pub fn create_funded_anchor(env: &Env, client: &SettlementContractClient, token: &Address, float: i128) -> Address {
+    let anchor = Address::generate(env);
+    client.add_anchor(&anchor);
+
+    if float > 0 {
+        token::StellarAssetClient::new(env, token).mint(&anchor, &float);
+        client.deposit_float(&anchor, token, &float);
+    }
+
+    anchor
+}
+
+/// Deterministic payment reference
+pub fn reference(env: &Env, n: u32) -> BytesN<32> {
+    let mut bytes = [0u8; 32];
+    bytes[..4].copy_from_slice(&n.to_be_bytes());
+    BytesN::from_array(env, &bytes)
+}
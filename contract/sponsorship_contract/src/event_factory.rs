@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env, Vec};
+
+/// Subset of the event factory interface used to check who organizes an event
+#[allow(dead_code)]
+#[contractclient(name = "EventFactoryClient")]
+pub trait EventFactoryInterface {
+    fn get_events_by_organizer(env: Env, organizer: Address) -> Vec<Address>;
+}
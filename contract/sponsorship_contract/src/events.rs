@@ -0,0 +1,46 @@
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::storage_types::SponsorTier;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SponsoredEvent {
+    pub event: Address,
+    pub sponsor: Address,
+    pub tier: SponsorTier,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct FundsReleasedEvent {
+    pub event: Address,
+    pub organizer: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SponsorRefundedEvent {
+    pub event: Address,
+    pub sponsor: Address,
+    pub amount: i128,
+}
+
+#[allow(deprecated)]
+pub fn emit_sponsored(env: &Env, event: SponsoredEvent) {
+    env.events()
+        .publish((Symbol::new(env, "sponsored"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_funds_released(env: &Env, event: FundsReleasedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "funds_released"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_sponsor_refunded(env: &Env, event: SponsorRefundedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "sponsor_refunded"),), event);
+}
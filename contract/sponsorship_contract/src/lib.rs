@@ -0,0 +1,284 @@
+#![no_std]
+
+mod event_factory;
+mod events;
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+use soroban_sdk::{contract, contractimpl, token, Address, Env, Symbol, Vec};
+
+pub use storage_types::*;
+
+/// Time after the event ends during which the organizer can release sponsor funds.
+/// Once it lapses without a release, sponsors can reclaim their funding.
+const RELEASE_WINDOW: u64 = 30 * 86400;
+
+#[contract]
+pub struct SponsorshipContract;
+
+#[contractimpl]
+impl SponsorshipContract {
+    /// Initialize the contract with an admin and the event factory that records event organizers
+    pub fn initialize(env: Env, admin: Address, event_factory: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::EventFactory, &event_factory);
+    }
+
+    /// Point ownership checks at a new event factory
+    pub fn set_event_factory(env: Env, event_factory: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::EventFactory, &event_factory);
+    }
+
+    /// Open sponsorships for an event, paid in `token` and escrowed until after `end_time`.
+    /// The event factory must list `event` among the organizer's events.
+    pub fn register_event(env: Env, organizer: Address, event: Address, token: Address, end_time: u64) {
+        organizer.require_auth();
+
+        let factory: Address = env.storage().instance().get(&DataKey::EventFactory).unwrap();
+        let organized = event_factory::EventFactoryClient::new(&env, &factory).get_events_by_organizer(&organizer);
+        if !organized.contains(&event) {
+            panic!("Not the event organizer");
+        }
+
+        if env.storage().persistent().has(&DataKey::Event(event.clone())) {
+            panic!("Event already registered");
+        }
+        if end_time <= env.ledger().timestamp() {
+            panic!("End time must be in the future");
+        }
+
+        let config = EventSponsorship {
+            event: event.clone(),
+            organizer,
+            token,
+            end_time,
+            escrowed: 0,
+            cancelled: false,
+            released: false,
+        };
+
+        env.storage().persistent().set(&DataKey::Event(event), &config);
+    }
+
+    /// Configure a tier's price, available slots and the perks it grants
+    pub fn set_tier(
+        env: Env,
+        event: Address,
+        tier: SponsorTier,
+        price: i128,
+        slots: u32,
+        perks: Vec<Symbol>,
+    ) {
+        let config = read_event(&env, &event);
+        config.organizer.require_auth();
+
+        if price <= 0 {
+            panic!("Price must be positive");
+        }
+
+        let sold = read_tier(&env, &event, tier).map(|t| t.sold).unwrap_or(0);
+        if slots < sold {
+            panic!("Slots below sold count");
+        }
+
+        env.storage().persistent().set(
+            &DataKey::Tier(event, tier),
+            &TierConfig {
+                price,
+                slots,
+                sold,
+                perks,
+            },
+        );
+    }
+
+    /// Buy a sponsorship tier; the payment is held in escrow until the event is settled
+    pub fn sponsor(env: Env, sponsor: Address, event: Address, tier: SponsorTier) {
+        sponsor.require_auth();
+
+        let mut config = read_event(&env, &event);
+        if config.cancelled {
+            panic!("Event cancelled");
+        }
+        if env.ledger().timestamp() >= config.end_time {
+            panic!("Event has ended");
+        }
+
+        let key = DataKey::Sponsorship(event.clone(), sponsor.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("Already a sponsor");
+        }
+
+        let mut tier_config = read_tier(&env, &event, tier).expect("Tier not offered");
+        if tier_config.sold >= tier_config.slots {
+            panic!("Tier sold out");
+        }
+
+        let token_client = token::TokenClient::new(&env, &config.token);
+        token_client.transfer(&sponsor, env.current_contract_address(), &tier_config.price);
+
+        tier_config.sold += 1;
+        config.escrowed += tier_config.price;
+
+        let sponsorship = Sponsorship {
+            sponsor: sponsor.clone(),
+            tier,
+            amount: tier_config.price,
+            perks: tier_config.perks.clone(),
+            refunded: false,
+        };
+
+        env.storage().persistent().set(&key, &sponsorship);
+        env.storage().persistent().set(&DataKey::Tier(event.clone(), tier), &tier_config);
+        env.storage().persistent().set(&DataKey::Event(event.clone()), &config);
+
+        events::emit_sponsored(
+            &env,
+            events::SponsoredEvent {
+                event,
+                sponsor,
+                tier,
+                amount: sponsorship.amount,
+            },
+        );
+    }
+
+    /// Cancel the event so every sponsor can reclaim their funding
+    pub fn cancel_event(env: Env, event: Address) {
+        let mut config = read_event(&env, &event);
+        config.organizer.require_auth();
+
+        if config.released {
+            panic!("Funds already released");
+        }
+
+        config.cancelled = true;
+        env.storage().persistent().set(&DataKey::Event(event), &config);
+    }
+
+    /// Release escrowed sponsor funds to the organizer once the event has taken place
+    pub fn release_funds(env: Env, event: Address) -> i128 {
+        let mut config = read_event(&env, &event);
+        config.organizer.require_auth();
+
+        let current_time = env.ledger().timestamp();
+        if config.cancelled {
+            panic!("Event cancelled");
+        }
+        if config.released {
+            panic!("Funds already released");
+        }
+        if current_time < config.end_time {
+            panic!("Event has not ended");
+        }
+        if current_time > config.end_time + RELEASE_WINDOW {
+            panic!("Release window has passed");
+        }
+
+        let amount = config.escrowed;
+        config.escrowed = 0;
+        config.released = true;
+        env.storage().persistent().set(&DataKey::Event(event.clone()), &config);
+
+        if amount > 0 {
+            let token_client = token::TokenClient::new(&env, &config.token);
+            token_client.transfer(&env.current_contract_address(), &config.organizer, &amount);
+        }
+
+        events::emit_funds_released(
+            &env,
+            events::FundsReleasedEvent {
+                event,
+                organizer: config.organizer,
+                amount,
+            },
+        );
+
+        amount
+    }
+
+    /// Reclaim sponsorship funding from a cancelled event, or one whose organizer never released it
+    pub fn refund(env: Env, sponsor: Address, event: Address) -> i128 {
+        sponsor.require_auth();
+
+        let mut config = read_event(&env, &event);
+        let refundable = config.cancelled
+            || (!config.released && env.ledger().timestamp() > config.end_time + RELEASE_WINDOW);
+        if !refundable {
+            panic!("Sponsorship not refundable");
+        }
+
+        let key = DataKey::Sponsorship(event.clone(), sponsor.clone());
+        let mut sponsorship: Sponsorship = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Sponsorship not found");
+
+        if sponsorship.refunded {
+            panic!("Already refunded");
+        }
+
+        sponsorship.refunded = true;
+        config.escrowed -= sponsorship.amount;
+        env.storage().persistent().set(&key, &sponsorship);
+        env.storage().persistent().set(&DataKey::Event(event.clone()), &config);
+
+        let token_client = token::TokenClient::new(&env, &config.token);
+        token_client.transfer(&env.current_contract_address(), &sponsor, &sponsorship.amount);
+
+        events::emit_sponsor_refunded(
+            &env,
+            events::SponsorRefundedEvent {
+                event,
+                sponsor,
+                amount: sponsorship.amount,
+            },
+        );
+
+        sponsorship.amount
+    }
+
+    /// Get an event's sponsorship state
+    pub fn get_event(env: Env, event: Address) -> Option<EventSponsorship> {
+        env.storage().persistent().get(&DataKey::Event(event))
+    }
+
+    /// Get a tier's configuration
+    pub fn get_tier(env: Env, event: Address, tier: SponsorTier) -> Option<TierConfig> {
+        read_tier(&env, &event, tier)
+    }
+
+    /// Get the event factory used for ownership checks
+    pub fn get_event_factory(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::EventFactory).unwrap()
+    }
+
+    /// Get a sponsor's sponsorship and the perks it grants
+    pub fn get_sponsorship(env: Env, event: Address, sponsor: Address) -> Option<Sponsorship> {
+        env.storage().persistent().get(&DataKey::Sponsorship(event, sponsor))
+    }
+}
+
+fn read_event(env: &Env, event: &Address) -> EventSponsorship {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Event(event.clone()))
+        .expect("Event not registered")
+}
+
+fn read_tier(env: &Env, event: &Address, tier: SponsorTier) -> Option<TierConfig> {
+    env.storage().persistent().get(&DataKey::Tier(event.clone(), tier))
+}
@@ -0,0 +1,50 @@
+use soroban_sdk::{contracttype, Address, Symbol, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    EventFactory, // Source of truth for which organizer owns an event contract
+    Event(Address),
+    Tier(Address, SponsorTier),    // (Event, Tier) -> TierConfig
+    Sponsorship(Address, Address), // (Event, Sponsor) -> Sponsorship
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[contracttype]
+pub enum SponsorTier {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct EventSponsorship {
+    pub event: Address,
+    pub organizer: Address,
+    pub token: Address,
+    pub end_time: u64,
+    pub escrowed: i128,
+    pub cancelled: bool,
+    pub released: bool,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct TierConfig {
+    pub price: i128,
+    pub slots: u32,
+    pub sold: u32,
+    pub perks: Vec<Symbol>,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Sponsorship {
+    pub sponsor: Address,
+    pub tier: SponsorTier,
+    pub amount: i128,
+    pub perks: Vec<Symbol>,
+    pub refunded: bool,
+}
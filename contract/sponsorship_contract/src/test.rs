@@ -0,0 +1,239 @@
+#![cfg(test)]
+
+use super::*;
+use crate::testutils::create_event_factory;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const END_TIME: u64 = 10_000;
+
+fn create_token_contract<'a>(e: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
+    token::StellarAssetClient::new(e, &e.register_stellar_asset_contract_v2(admin.clone()).address())
+}
+
+fn setup<'a>(
+    env: &Env,
+) -> (SponsorshipContractClient<'a>, token::StellarAssetClient<'a>, Address, Address) {
+    let admin = Address::generate(env);
+    let organizer = Address::generate(env);
+    let event = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = create_token_contract(env, &token_admin);
+    let contract = SponsorshipContractClient::new(env, &env.register(SponsorshipContract, ()));
+    let factory = create_event_factory(env);
+
+    factory.add_event(&organizer, &event);
+    contract.initialize(&admin, &factory.address);
+    contract.register_event(&organizer, &event, &token.address, &END_TIME);
+    contract.set_tier(
+        &event,
+        &SponsorTier::Gold,
+        &5000,
+        &1,
+        &Vec::from_array(env, [symbol_short!("keynote"), symbol_short!("booth")]),
+    );
+    contract.set_tier(
+        &event,
+        &SponsorTier::Bronze,
+        &1000,
+        &10,
+        &Vec::from_array(env, [symbol_short!("logo")]),
+    );
+
+    (contract, token, organizer, event)
+}
+
+#[test]
+fn test_sponsor_and_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, organizer, event) = setup(&env);
+    let token_client = token::Client::new(&env, &token.address);
+    let gold = Address::generate(&env);
+    let bronze = Address::generate(&env);
+    token.mint(&gold, &5000);
+    token.mint(&bronze, &1000);
+
+    contract.sponsor(&gold, &event, &SponsorTier::Gold);
+    contract.sponsor(&bronze, &event, &SponsorTier::Bronze);
+
+    let sponsorship = contract.get_sponsorship(&event, &gold).unwrap();
+    assert_eq!(sponsorship.perks.len(), 2);
+    assert_eq!(contract.get_event(&event).unwrap().escrowed, 6000);
+    assert_eq!(contract.get_tier(&event, &SponsorTier::Bronze).unwrap().sold, 1);
+
+    env.ledger().set_timestamp(END_TIME);
+    assert_eq!(contract.release_funds(&event), 6000);
+    assert_eq!(token_client.balance(&organizer), 6000);
+}
+
+#[test]
+fn test_refund_after_cancellation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, event) = setup(&env);
+    let token_client = token::Client::new(&env, &token.address);
+    let sponsor = Address::generate(&env);
+    token.mint(&sponsor, &1000);
+
+    contract.sponsor(&sponsor, &event, &SponsorTier::Bronze);
+    contract.cancel_event(&event);
+
+    assert_eq!(contract.refund(&sponsor, &event), 1000);
+    assert_eq!(token_client.balance(&sponsor), 1000);
+    assert_eq!(contract.get_event(&event).unwrap().escrowed, 0);
+}
+
+#[test]
+fn test_refund_after_release_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, event) = setup(&env);
+    let sponsor = Address::generate(&env);
+    token.mint(&sponsor, &5000);
+
+    contract.sponsor(&sponsor, &event, &SponsorTier::Gold);
+
+    env.ledger().set_timestamp(END_TIME + 30 * 86400 + 1);
+    assert_eq!(contract.refund(&sponsor, &event), 5000);
+}
+
+#[test]
+#[should_panic(expected = "Tier sold out")]
+fn test_tier_sold_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, event) = setup(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    token.mint(&first, &5000);
+    token.mint(&second, &5000);
+
+    contract.sponsor(&first, &event, &SponsorTier::Gold);
+    contract.sponsor(&second, &event, &SponsorTier::Gold);
+}
+
+#[test]
+#[should_panic(expected = "Sponsorship not refundable")]
+fn test_no_refund_while_event_live() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, _organizer, event) = setup(&env);
+    let sponsor = Address::generate(&env);
+    token.mint(&sponsor, &1000);
+
+    contract.sponsor(&sponsor, &event, &SponsorTier::Bronze);
+    contract.refund(&sponsor, &event);
+}
+
+#[test]
+#[should_panic(expected = "Not the event organizer")]
+fn test_register_event_requires_ownership() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let squatter = Address::generate(&env);
+    let event = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = SponsorshipContractClient::new(&env, &env.register(SponsorshipContract, ()));
+    let factory = create_event_factory(&env);
+
+    factory.add_event(&organizer, &event);
+    contract.initialize(&admin, &factory.address);
+
+    // Registering someone else's event would route its sponsor funds to the squatter
+    contract.register_event(&squatter, &event, &token.address, &END_TIME);
+}
+
+#[test]
+fn test_set_event_factory() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (contract, token, organizer, _event) = setup(&env);
+    let new_factory = create_event_factory(&env);
+    let new_event = Address::generate(&env);
+    new_factory.add_event(&organizer, &new_event);
+
+    contract.set_event_factory(&new_factory.address);
+    assert_eq!(contract.get_event_factory(), new_factory.address);
+
+    contract.register_event(&organizer, &new_event, &token.address, &END_TIME);
+    assert_eq!(contract.get_event(&new_event).unwrap().organizer, organizer);
+}
+
+mod properties {
+    extern crate std;
+
+    use crate::testutils::create_sponsored_event;
+    use crate::SponsorTier;
+    use proptest::prelude::*;
+    use test_utils::{create_token, set_time, setup_env, DAY};
+
+    const TIERS: [SponsorTier; 3] = [SponsorTier::Bronze, SponsorTier::Silver, SponsorTier::Gold];
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        /// Escrow always matches the contract's balance, tiers never oversell, and however the
+        /// event settles, every sponsor payment ends up with the organizer or back with its sponsor.
+        #[test]
+        fn prop_escrow_matches_sponsor_payments(
+            prices in proptest::collection::vec(1i128..10_000, 3),
+            slots in proptest::collection::vec(0u32..4, 3),
+            purchases in proptest::collection::vec(0usize..3, 1..10),
+            outcome in 0u8..3,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            let fixture = create_sponsored_event(&env, &token.address, 10_000);
+            for (i, tier) in TIERS.iter().enumerate() {
+                fixture.set_tier(&env, *tier, prices[i], slots[i]);
+            }
+
+            let mut sponsors = std::vec::Vec::new();
+            let mut paid = 0;
+            for tier in purchases.iter() {
+                let (sponsor, sponsored) = fixture.try_sponsor(&env, TIERS[*tier]);
+                if sponsored {
+                    paid += prices[*tier];
+                    sponsors.push(sponsor);
+                }
+
+                let tier_config = fixture.contract.get_tier(&fixture.event, &TIERS[*tier]).unwrap();
+                prop_assert!(tier_config.sold <= tier_config.slots);
+                prop_assert_eq!(fixture.contract.get_event(&fixture.event).unwrap().escrowed, paid);
+                prop_assert_eq!(token.balance(&fixture.contract.address), paid);
+            }
+
+            match outcome {
+                0 => {
+                    set_time(&env, 10_000);
+                    prop_assert_eq!(fixture.contract.release_funds(&fixture.event), paid);
+                }
+                1 => fixture.contract.cancel_event(&fixture.event),
+                _ => set_time(&env, 10_000 + 31 * DAY),
+            }
+
+            for sponsor in sponsors.iter() {
+                let _ = fixture.contract.try_refund(sponsor, &fixture.event);
+            }
+
+            let refunded: i128 = sponsors.iter().map(|sponsor| token.balance(sponsor)).sum();
+            prop_assert_eq!(refunded + token.balance(&fixture.organizer), paid);
+            prop_assert_eq!(token.balance(&fixture.contract.address), 0);
+            prop_assert_eq!(fixture.contract.get_event(&fixture.event).unwrap().escrowed, 0);
+        }
+    }
+}
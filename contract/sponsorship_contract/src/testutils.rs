@@ -0,0 +1,81 @@
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, token, Address, Env, Vec};
+
+use crate::{SponsorTier, SponsorshipContract, SponsorshipContractClient};
+
+/// Stand-in for the event factory's organizer index
+#[contract]
+pub struct MockEventFactory;
+
+#[contractimpl]
+impl MockEventFactory {
+    pub fn add_event(env: Env, organizer: Address, event: Address) {
+        let mut events = Self::get_events_by_organizer(env.clone(), organizer.clone());
+        events.push_back(event);
+        env.storage().persistent().set(&organizer, &events);
+    }
+
+    pub fn get_events_by_organizer(env: Env, organizer: Address) -> Vec<Address> {
+        env.storage().persistent().get(&organizer).unwrap_or(Vec::new(&env))
+    }
+}
+
+/// Register an empty mock event factory
+pub fn create_event_factory<'a>(env: &Env) -> MockEventFactoryClient<'a> {
+    MockEventFactoryClient::new(env, &env.register(MockEventFactory, ()))
+}
+
+/// Register and initialize a sponsorship contract checking ownership against `event_factory`
+pub fn create_sponsorship_contract<'a>(
+    env: &Env,
+    admin: &Address,
+    event_factory: &Address,
+) -> SponsorshipContractClient<'a> {
+    let client = SponsorshipContractClient::new(env, &env.register(SponsorshipContract, ()));
+    client.initialize(admin, event_factory);
+    client
+}
+
+/// An event open for sponsorship, with a fresh organizer
+pub struct EventFixture<'a> {
+    pub contract: SponsorshipContractClient<'a>,
+    pub event: Address,
+    pub organizer: Address,
+    pub token: Address,
+}
+
+/// Record a fresh event for a fresh organizer in a mock factory and register it for
+/// sponsorship, ending at `end_time`. Requires mocked auths.
+pub fn create_sponsored_event<'a>(env: &Env, token: &Address, end_time: u64) -> EventFixture<'a> {
+    let factory = create_event_factory(env);
+    let contract = create_sponsorship_contract(env, &Address::generate(env), &factory.address);
+    let organizer = Address::generate(env);
+    let event = Address::generate(env);
+
+    factory.add_event(&organizer, &event);
+    contract.register_event(&organizer, &event, token, &end_time);
+
+    EventFixture {
+        contract,
+        event,
+        organizer,
+        token: token.clone(),
+    }
+}
+
+impl EventFixture<'_> {
+    /// Offer a tier with no perks
+    pub fn set_tier(&self, env: &Env, tier: SponsorTier, price: i128, slots: u32) {
+        self.contract.set_tier(&self.event, &tier, &price, &slots, &Vec::new(env));
+    }
+
+    /// Mint the tier price to a fresh sponsor, who then tries to buy the tier.
+    /// Returns the sponsor and whether the purchase went through.
+    pub fn try_sponsor(&self, env: &Env, tier: SponsorTier) -> (Address, bool) {
+        let sponsor = Address::generate(env);
+        let price = self.contract.get_tier(&self.event, &tier).unwrap().price;
+        token::StellarAssetClient::new(env, &self.token).mint(&sponsor, &price);
+
+        let sponsored = self.contract.try_sponsor(&sponsor, &self.event, &tier).is_ok();
+        (sponsor, sponsored)
+    }
+}
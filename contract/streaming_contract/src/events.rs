@@ -0,0 +1,47 @@
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub struct StreamCreatedEvent {
+    pub stream_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub deposit: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct StreamWithdrawnEvent {
+    pub stream_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct StreamCancelledEvent {
+    pub stream_id: u64,
+    pub cancelled_by: Address,
+    pub recipient_amount: i128,
+    pub sender_refund: i128,
+}
+
+#[allow(deprecated)]
+pub fn emit_stream_created(env: &Env, event: StreamCreatedEvent) {
+    env.events()
+        .publish((Symbol::new(env, "stream_created"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_stream_withdrawn(env: &Env, event: StreamWithdrawnEvent) {
+    env.events()
+        .publish((Symbol::new(env, "stream_withdrawn"),), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_stream_cancelled(env: &Env, event: StreamCancelledEvent) {
+    env.events()
+        .publish((Symbol::new(env, "stream_cancelled"),), event);
+}
@@ -0,0 +1,189 @@
+#![no_std]
+
+mod events;
+mod storage_types;
+
+#[cfg(test)]
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+pub use storage_types::*;
+
+#[contract]
+pub struct StreamingContract;
+
+#[contractimpl]
+impl StreamingContract {
+    /// Create a stream that pays `deposit` to `recipient` linearly between `start_time` and `end_time`
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        deposit: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> u64 {
+        sender.require_auth();
+
+        if deposit <= 0 {
+            panic!("Deposit must be positive");
+        }
+        if start_time >= end_time {
+            panic!("Invalid time range");
+        }
+        if end_time <= env.ledger().timestamp() {
+            panic!("Stream already ended");
+        }
+        if sender == recipient {
+            panic!("Sender cannot be recipient");
+        }
+
+        let token_client = token::TokenClient::new(&env, &token);
+        token_client.transfer(&sender, env.current_contract_address(), &deposit);
+
+        let stream_id: u64 = env.storage().instance().get(&DataKey::NextStreamId).unwrap_or(1);
+
+        let stream = Stream {
+            stream_id,
+            sender: sender.clone(),
+            recipient: recipient.clone(),
+            token,
+            deposit,
+            withdrawn: 0,
+            start_time,
+            end_time,
+            status: StreamStatus::Active,
+        };
+
+        env.storage().persistent().set(&DataKey::Stream(stream_id), &stream);
+        env.storage().instance().set(&DataKey::NextStreamId, &(stream_id + 1));
+
+        events::emit_stream_created(
+            &env,
+            events::StreamCreatedEvent {
+                stream_id,
+                sender,
+                recipient,
+                deposit,
+                start_time,
+                end_time,
+            },
+        );
+
+        stream_id
+    }
+
+    /// Withdraw everything streamed to the recipient so far
+    pub fn withdraw(env: Env, stream_id: u64) -> i128 {
+        let mut stream = read_stream(&env, stream_id);
+        stream.recipient.require_auth();
+
+        if stream.status != StreamStatus::Active {
+            panic!("Stream is not active");
+        }
+
+        let amount = streamed_amount(&env, &stream) - stream.withdrawn;
+        if amount <= 0 {
+            panic!("Nothing to withdraw");
+        }
+
+        stream.withdrawn += amount;
+        if stream.withdrawn == stream.deposit {
+            stream.status = StreamStatus::Completed;
+        }
+        env.storage().persistent().set(&DataKey::Stream(stream_id), &stream);
+
+        let token_client = token::TokenClient::new(&env, &stream.token);
+        token_client.transfer(&env.current_contract_address(), &stream.recipient, &amount);
+
+        events::emit_stream_withdrawn(
+            &env,
+            events::StreamWithdrawnEvent {
+                stream_id,
+                recipient: stream.recipient,
+                amount,
+            },
+        );
+
+        amount
+    }
+
+    /// Cancel a stream; the recipient receives what has streamed so far and the sender the rest
+    pub fn cancel_stream(env: Env, caller: Address, stream_id: u64) {
+        caller.require_auth();
+
+        let mut stream = read_stream(&env, stream_id);
+        if caller != stream.sender && caller != stream.recipient {
+            panic!("Not authorized");
+        }
+        if stream.status != StreamStatus::Active {
+            panic!("Stream is not active");
+        }
+
+        let streamed = streamed_amount(&env, &stream);
+        let recipient_amount = streamed - stream.withdrawn;
+        let sender_refund = stream.deposit - streamed;
+
+        stream.withdrawn = streamed;
+        stream.status = StreamStatus::Cancelled;
+        env.storage().persistent().set(&DataKey::Stream(stream_id), &stream);
+
+        let token_client = token::TokenClient::new(&env, &stream.token);
+        if recipient_amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &stream.recipient, &recipient_amount);
+        }
+        if sender_refund > 0 {
+            token_client.transfer(&env.current_contract_address(), &stream.sender, &sender_refund);
+        }
+
+        events::emit_stream_cancelled(
+            &env,
+            events::StreamCancelledEvent {
+                stream_id,
+                cancelled_by: caller,
+                recipient_amount,
+                sender_refund,
+            },
+        );
+    }
+
+    /// Get stream details
+    pub fn get_stream(env: Env, stream_id: u64) -> Option<Stream> {
+        env.storage().persistent().get(&DataKey::Stream(stream_id))
+    }
+
+    /// Amount the recipient could withdraw right now
+    pub fn withdrawable_amount(env: Env, stream_id: u64) -> i128 {
+        let stream = read_stream(&env, stream_id);
+        if stream.status != StreamStatus::Active {
+            return 0;
+        }
+        streamed_amount(&env, &stream) - stream.withdrawn
+    }
+}
+
+fn read_stream(env: &Env, stream_id: u64) -> Stream {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Stream(stream_id))
+        .expect("Stream not found")
+}
+
+/// Total amount vested to the recipient at the current ledger time
+fn streamed_amount(env: &Env, stream: &Stream) -> i128 {
+    let now = env.ledger().timestamp();
+    if now <= stream.start_time {
+        return 0;
+    }
+    if now >= stream.end_time {
+        return stream.deposit;
+    }
+
+    let elapsed = (now - stream.start_time) as i128;
+    let duration = (stream.end_time - stream.start_time) as i128;
+    (stream.deposit * elapsed) / duration
+}
@@ -0,0 +1,30 @@
+use soroban_sdk::{contracttype, Address};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    NextStreamId,
+    Stream(u64),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[contracttype]
+pub enum StreamStatus {
+    Active,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Stream {
+    pub stream_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub deposit: i128,
+    pub withdrawn: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub status: StreamStatus,
+}
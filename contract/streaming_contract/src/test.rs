@@ -0,0 +1,153 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+fn create_token_contract<'a>(e: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
+    token::StellarAssetClient::new(e, &e.register_stellar_asset_contract_v2(admin.clone()).address())
+}
+
+fn create_streaming_contract<'a>(e: &Env) -> StreamingContractClient<'a> {
+    StreamingContractClient::new(e, &e.register(StreamingContract, ()))
+}
+
+#[test]
+fn test_withdraw_accrued() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_streaming_contract(&env);
+
+    token.mint(&sender, &10000);
+
+    let stream_id = contract.create_stream(&sender, &recipient, &token.address, &10000, &1000, &2000);
+
+    env.ledger().set_timestamp(1250);
+    assert_eq!(contract.withdrawable_amount(&stream_id), 2500);
+    assert_eq!(contract.withdraw(&stream_id), 2500);
+    assert_eq!(token_client.balance(&recipient), 2500);
+
+    env.ledger().set_timestamp(3000);
+    assert_eq!(contract.withdraw(&stream_id), 7500);
+    assert_eq!(token_client.balance(&recipient), 10000);
+    assert_eq!(contract.get_stream(&stream_id).unwrap().status, StreamStatus::Completed);
+}
+
+#[test]
+fn test_cancel_splits_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_streaming_contract(&env);
+
+    token.mint(&sender, &10000);
+
+    let stream_id = contract.create_stream(&sender, &recipient, &token.address, &10000, &1000, &2000);
+
+    env.ledger().set_timestamp(1200);
+    contract.withdraw(&stream_id);
+
+    env.ledger().set_timestamp(1600);
+    contract.cancel_stream(&sender, &stream_id);
+
+    assert_eq!(token_client.balance(&recipient), 6000);
+    assert_eq!(token_client.balance(&sender), 4000);
+    assert_eq!(token_client.balance(&contract.address), 0);
+    assert_eq!(contract.get_stream(&stream_id).unwrap().status, StreamStatus::Cancelled);
+}
+
+#[test]
+#[should_panic(expected = "Nothing to withdraw")]
+fn test_withdraw_before_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_streaming_contract(&env);
+
+    token.mint(&sender, &10000);
+
+    let stream_id = contract.create_stream(&sender, &recipient, &token.address, &10000, &5000, &6000);
+    contract.withdraw(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_cancel_by_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_streaming_contract(&env);
+
+    token.mint(&sender, &10000);
+
+    let stream_id = contract.create_stream(&sender, &recipient, &token.address, &10000, &0, &1000);
+    contract.cancel_stream(&stranger, &stream_id);
+}
+
+mod properties {
+    use crate::testutils::create_funded_stream;
+    use proptest::prelude::*;
+    use test_utils::{create_token, set_time, setup_env};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// However a stream is withdrawn from and cancelled, the deposit is split exactly
+        /// between recipient and sender and nothing is left in the contract.
+        #[test]
+        fn prop_cancel_distributes_full_deposit(
+            deposit in 1i128..1_000_000_000_000,
+            duration in 1u64..1_000_000,
+            withdraw_offset in 0u64..2_000_000,
+            cancel_delay in 0u64..2_000_000,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            set_time(&env, 1000);
+
+            let stream = create_funded_stream(&env, &token.address, deposit, 1000, 1000 + duration);
+
+            set_time(&env, 1000 + withdraw_offset);
+            if stream.contract.withdrawable_amount(&stream.stream_id) > 0 {
+                stream.contract.withdraw(&stream.stream_id);
+            }
+
+            set_time(&env, 1000 + withdraw_offset + cancel_delay);
+            let _ = stream.contract.try_cancel_stream(&stream.sender, &stream.stream_id);
+
+            let withdrawn = stream.contract.get_stream(&stream.stream_id).unwrap().withdrawn;
+            prop_assert!(withdrawn <= deposit);
+            prop_assert_eq!(token.balance(&stream.recipient), withdrawn);
+            prop_assert_eq!(
+                token.balance(&stream.recipient) + token.balance(&stream.sender)
+                    + token.balance(&stream.contract.address),
+                deposit
+            );
+        }
+    }
+}
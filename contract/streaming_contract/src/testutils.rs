@@ -0,0 +1,40 @@
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+use crate::{StreamingContract, StreamingContractClient};
+
+/// Register a streaming contract
+pub fn create_streaming_contract<'a>(env: &Env) -> StreamingContractClient<'a> {
+    StreamingContractClient::new(env, &env.register(StreamingContract, ()))
+}
+
+/// A stream between freshly generated parties, funded with `deposit` of `token`
+pub struct StreamFixture<'a> {
+    pub contract: StreamingContractClient<'a>,
+    pub stream_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+}
+
+/// Mint `deposit` to a new sender and open a stream over `[start_time, end_time]`.
+/// Requires mocked auths.
+pub fn create_funded_stream<'a>(
+    env: &Env,
+    token: &Address,
+    deposit: i128,
+    start_time: u64,
+    end_time: u64,
+) -> StreamFixture<'a> {
+    let sender = Address::generate(env);
+    let recipient = Address::generate(env);
+    let contract = create_streaming_contract(env);
+
+    token::StellarAssetClient::new(env, token).mint(&sender, &deposit);
+    let stream_id = contract.create_stream(&sender, &recipient, token, &deposit, &start_time, &end_time);
+
+    StreamFixture {
+        contract,
+        stream_id,
+        sender,
+        recipient,
+    }
+}
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracttype, Address, Symbol};
+use soroban_sdk::{contracttype, Address, String, Symbol, Vec};
+
+use crate::storage_types::SubscriptionStatus;
 
 #[contracttype]
 #[derive(Clone)]
@@ -69,6 +71,132 @@ pub struct GiftSubscriptionCreatedEvent {
     pub plan_id: u32,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct GiftDeliverableEvent {
+    pub gift_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub plan_id: u32,
+    pub message: String,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SubscriptionTransferredEvent {
+    pub subscription_id: u64,
+    pub from: Address,
+    pub to: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PlanMigratedEvent {
+    pub subscription_id: u64,
+    pub user: Address,
+    pub old_plan_id: u32,
+    pub new_plan_id: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct UsageRecordedEvent {
+    pub subscription_id: u64,
+    pub user: Address,
+    pub meter_id: Symbol,
+    pub units: u64,
+    pub total: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SubscriptionStatusChangedEvent {
+    pub subscription_id: u64,
+    pub user: Address,
+    pub old_status: SubscriptionStatus,
+    pub new_status: SubscriptionStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AdminProposedEvent {
+    pub current_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AdminTransferredEvent {
+    pub old_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SubscriptionSuspendedEvent {
+    pub subscription_id: Option<u64>, // None when the user only shares someone else's subscription
+    pub user: Address,
+    pub reason: String,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SubscriptionUnsuspendedEvent {
+    pub subscription_id: Option<u64>,
+    pub user: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BundleCreatedEvent {
+    pub bundle_id: u32,
+    pub name: String,
+    pub category_ids: Vec<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BundleUpdatedEvent {
+    pub bundle_id: u32,
+    pub category_ids: Vec<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SeatAssignedEvent {
+    pub subscription_id: u64,
+    pub owner: Address,
+    pub member: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RenewalDueEvent {
+    pub subscription_id: u64,
+    pub user: Address,
+    pub due_at: u64,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RenewalFailedEvent {
+    pub subscription_id: u64,
+    pub user: Address,
+    pub amount_due: i128,
+    pub attempt: u32,
+    pub next_retry_at: Option<u64>, // None once retries are exhausted and the subscription expired
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RevenueWithdrawnEvent {
+    pub token: Address,
+    pub amount: i128,
+    pub to: Address,
+}
+
+#[allow(deprecated)]
 pub fn emit_subscription_created(
     env: &soroban_sdk::Env,
     event: SubscriptionCreatedEvent,
@@ -79,6 +207,7 @@ pub fn emit_subscription_created(
     );
 }
 
+#[allow(deprecated)]
 pub fn emit_subscription_renewed(
     env: &soroban_sdk::Env,
     event: SubscriptionRenewedEvent,
@@ -89,6 +218,7 @@ pub fn emit_subscription_renewed(
     );
 }
 
+#[allow(deprecated)]
 pub fn emit_subscription_cancelled(
     env: &soroban_sdk::Env,
     event: SubscriptionCancelledEvent,
@@ -99,6 +229,7 @@ pub fn emit_subscription_cancelled(
     );
 }
 
+#[allow(deprecated)]
 pub fn emit_subscription_paused(
     env: &soroban_sdk::Env,
     event: SubscriptionPausedEvent,
@@ -109,6 +240,7 @@ pub fn emit_subscription_paused(
     );
 }
 
+#[allow(deprecated)]
 pub fn emit_subscription_resumed(
     env: &soroban_sdk::Env,
     event: SubscriptionResumedEvent,
@@ -119,6 +251,7 @@ pub fn emit_subscription_resumed(
     );
 }
 
+#[allow(deprecated)]
 pub fn emit_subscription_upgraded(
     env: &soroban_sdk::Env,
     event: SubscriptionUpgradedEvent,
@@ -129,6 +262,7 @@ pub fn emit_subscription_upgraded(
     );
 }
 
+#[allow(deprecated)]
 pub fn emit_family_member_added(
     env: &soroban_sdk::Env,
     event: FamilyMemberAddedEvent,
@@ -139,6 +273,7 @@ pub fn emit_family_member_added(
     );
 }
 
+#[allow(deprecated)]
 pub fn emit_gift_subscription_created(
     env: &soroban_sdk::Env,
     event: GiftSubscriptionCreatedEvent,
@@ -148,3 +283,168 @@ pub fn emit_gift_subscription_created(
         event,
     );
 }
+
+#[allow(deprecated)]
+pub fn emit_revenue_withdrawn(
+    env: &soroban_sdk::Env,
+    event: RevenueWithdrawnEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "revenue_withdrawn"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_renewal_failed(
+    env: &soroban_sdk::Env,
+    event: RenewalFailedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "renewal_failed"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_subscription_transferred(
+    env: &soroban_sdk::Env,
+    event: SubscriptionTransferredEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "subscription_transferred"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_plan_migrated(
+    env: &soroban_sdk::Env,
+    event: PlanMigratedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "plan_migrated"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_usage_recorded(
+    env: &soroban_sdk::Env,
+    event: UsageRecordedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "usage_recorded"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_gift_deliverable(
+    env: &soroban_sdk::Env,
+    event: GiftDeliverableEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "gift_deliverable"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_subscription_status_changed(
+    env: &soroban_sdk::Env,
+    event: SubscriptionStatusChangedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "status_changed"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_renewal_due(
+    env: &soroban_sdk::Env,
+    event: RenewalDueEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "renewal_due"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_seat_assigned(
+    env: &soroban_sdk::Env,
+    event: SeatAssignedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "seat_assigned"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_bundle_created(
+    env: &soroban_sdk::Env,
+    event: BundleCreatedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "bundle_created"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_bundle_updated(
+    env: &soroban_sdk::Env,
+    event: BundleUpdatedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "bundle_updated"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_subscription_suspended(
+    env: &soroban_sdk::Env,
+    event: SubscriptionSuspendedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "subscription_suspended"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_subscription_unsuspended(
+    env: &soroban_sdk::Env,
+    event: SubscriptionUnsuspendedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "subscription_unsuspended"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_admin_proposed(
+    env: &soroban_sdk::Env,
+    event: AdminProposedEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "admin_proposed"),),
+        event,
+    );
+}
+
+#[allow(deprecated)]
+pub fn emit_admin_transferred(
+    env: &soroban_sdk::Env,
+    event: AdminTransferredEvent,
+) {
+    env.events().publish(
+        (Symbol::new(env, "admin_transferred"),),
+        event,
+    );
+}
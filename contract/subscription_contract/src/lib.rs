@@ -1,14 +1,25 @@
 #![no_std]
 
+mod analytics;
 mod events;
+mod loyalty;
+mod membership;
+mod metering;
+mod params;
+mod referral;
+mod rewards;
+mod stats;
 mod storage_types;
+mod subscribers;
 mod subscription;
 mod utils;
 
 #[cfg(test)]
 mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 
-use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, token, Address, Env, Map, String, Symbol, Vec};
 use storage_types::*;
 
 pub use subscription::*;
@@ -34,6 +45,49 @@ impl SubscriptionContract {
         env.storage().instance().set(&DataKey::NextSubscriptionId, &1u64);
     }
 
+    /// Propose a new admin; the change takes effect once they call `accept_admin`
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if new_admin == admin {
+            panic!("Already admin");
+        }
+
+        env.storage().instance().set(&DataKey::PendingAdmin, &new_admin);
+
+        events::emit_admin_proposed(
+            &env,
+            events::AdminProposedEvent {
+                current_admin: admin,
+                new_admin,
+            },
+        );
+    }
+
+    /// Accept a pending admin proposal; must be signed by the proposed admin
+    pub fn accept_admin(env: Env) {
+        let new_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingAdmin)
+            .expect("No pending admin");
+        new_admin.require_auth();
+
+        let old_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        events::emit_admin_transferred(
+            &env,
+            events::AdminTransferredEvent {
+                old_admin,
+                new_admin,
+            },
+        );
+    }
+
     /// Create a new subscription plan
     pub fn create_plan(
         env: Env,
@@ -56,10 +110,21 @@ impl SubscriptionContract {
             category_ids,
             max_family_members,
             is_active: true,
+            token_prices: Map::new(&env),
+            deprecated: false,
+            sunset_date: None,
+            successor_plan_id: None,
+            max_pause_days: 0,
+            max_pauses_per_term: 0,
+            refund_policy: RefundPolicy::Prorated,
+            max_seats: 0,
+            bundle_ids: Vec::new(&env),
+            price_version: 1,
         };
 
         env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
         env.storage().instance().set(&DataKey::NextPlanId, &(plan_id + 1));
+        subscription::record_price_version(&env, &plan);
 
         plan_id
     }
@@ -81,16 +146,488 @@ impl SubscriptionContract {
             .get(&DataKey::SubscriptionPlan(plan_id))
             .expect("Plan not found");
 
-        plan.price = price;
+        // Existing subscribers keep the price they paid until they renew
+        if price != plan.price {
+            // Grandfathered subscribers renew at a deprecated plan's last price
+            if plan.deprecated {
+                panic!("Deprecated plan prices are frozen");
+            }
+            plan.price = price;
+            plan.price_version += 1;
+            subscription::record_price_version(&env, &plan);
+        }
+
         plan.category_ids = category_ids;
         plan.is_active = is_active;
 
         env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
     }
 
+    /// Accept an additional payment token for a plan at the given price
+    pub fn set_plan_token_price(env: Env, plan_id: u32, token: Address, price: i128) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if price <= 0 {
+            panic!("Price must be positive");
+        }
+        if token == subscription::default_token(&env) {
+            panic!("Default token is priced by the plan");
+        }
+
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(plan_id))
+            .expect("Plan not found");
+
+        // Token prices are versioned like the default price, so paid terms stay pinned
+        if plan.token_prices.get(token.clone()) != Some(price) {
+            if plan.deprecated {
+                panic!("Deprecated plan prices are frozen");
+            }
+            plan.token_prices.set(token, price);
+            plan.price_version += 1;
+            subscription::record_price_version(&env, &plan);
+        }
+
+        env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
+    }
+
+    /// Stop accepting a payment token for a plan
+    pub fn remove_plan_token_price(env: Env, plan_id: u32, token: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(plan_id))
+            .expect("Plan not found");
+
+        if plan.token_prices.remove(token).is_some() {
+            if plan.deprecated {
+                panic!("Deprecated plan prices are frozen");
+            }
+            plan.price_version += 1;
+            subscription::record_price_version(&env, &plan);
+        }
+
+        env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
+    }
+
+    /// Set the shared referral contract, the share of referred users' payments sent to it,
+    /// and for how many terms after signup
+    pub fn set_referral_config(env: Env, referral_contract: Address, reward_bps: u32, reward_periods: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if reward_bps > 10000 {
+            panic!("Invalid reward share");
+        }
+
+        env.storage().instance().set(
+            &DataKey::ReferralConfig,
+            &ReferralConfig {
+                contract: referral_contract,
+                reward_bps,
+                reward_periods,
+            },
+        );
+    }
+
+    /// Set the loyalty discount schedule: each tier applies once a subscription has
+    /// renewed `min_renewals` times in a row. Tiers must be in ascending order.
+    pub fn set_loyalty_schedule(env: Env, tiers: Vec<LoyaltyTier>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut previous: Option<LoyaltyTier> = None;
+        for tier in tiers.iter() {
+            if tier.discount_bps > 10000 {
+                panic!("Invalid discount");
+            }
+            if let Some(previous) = previous {
+                if tier.min_renewals <= previous.min_renewals {
+                    panic!("Tiers must be in ascending order");
+                }
+            }
+            previous = Some(tier);
+        }
+
+        env.storage().instance().set(&DataKey::LoyaltySchedule, &tiers);
+    }
+
+    /// Limit how long and how often subscribers of a plan may pause (0 = unlimited)
+    pub fn set_pause_policy(env: Env, plan_id: u32, max_pause_days: u32, max_pauses_per_term: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(plan_id))
+            .expect("Plan not found");
+
+        plan.max_pause_days = max_pause_days;
+        plan.max_pauses_per_term = max_pauses_per_term;
+
+        env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
+    }
+
+    /// Set how cancellations on a plan are refunded
+    pub fn set_refund_policy(env: Env, plan_id: u32, refund_policy: RefundPolicy) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(plan_id))
+            .expect("Plan not found");
+
+        plan.refund_policy = refund_policy;
+
+        env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
+    }
+
+    /// Create a named category bundle that plans can reference
+    pub fn create_bundle(env: Env, name: String, category_ids: Vec<u32>) -> u32 {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let bundle_id: u32 = env.storage().instance().get(&DataKey::NextBundleId).unwrap_or(1);
+
+        let bundle = CategoryBundle {
+            bundle_id,
+            name: name.clone(),
+            category_ids: category_ids.clone(),
+        };
+
+        env.storage().persistent().set(&DataKey::Bundle(bundle_id), &bundle);
+        env.storage().instance().set(&DataKey::NextBundleId, &(bundle_id + 1));
+
+        events::emit_bundle_created(
+            &env,
+            events::BundleCreatedEvent {
+                bundle_id,
+                name,
+                category_ids,
+            },
+        );
+
+        bundle_id
+    }
+
+    /// Replace a bundle's categories; every plan referencing it picks up the change
+    pub fn update_bundle(env: Env, bundle_id: u32, category_ids: Vec<u32>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut bundle: CategoryBundle = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Bundle(bundle_id))
+            .expect("Bundle not found");
+
+        bundle.category_ids = category_ids.clone();
+
+        env.storage().persistent().set(&DataKey::Bundle(bundle_id), &bundle);
+
+        events::emit_bundle_updated(
+            &env,
+            events::BundleUpdatedEvent {
+                bundle_id,
+                category_ids,
+            },
+        );
+    }
+
+    /// Set the category bundles a plan grants access to
+    pub fn set_plan_bundles(env: Env, plan_id: u32, bundle_ids: Vec<u32>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(plan_id))
+            .expect("Plan not found");
+
+        for bundle_id in bundle_ids.iter() {
+            if !env.storage().persistent().has(&DataKey::Bundle(bundle_id)) {
+                panic!("Bundle not found");
+            }
+        }
+
+        plan.bundle_ids = bundle_ids;
+
+        env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
+    }
+
+    /// Discount paying `periods` terms of a plan upfront (0 bps removes the discount)
+    pub fn set_prepay_discount(env: Env, plan_id: u32, periods: u32, discount_bps: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::SubscriptionPlan(plan_id)) {
+            panic!("Plan not found");
+        }
+
+        if periods < 2 {
+            panic!("Prepay discounts need at least two periods");
+        }
+
+        if discount_bps > 10000 {
+            panic!("Invalid discount");
+        }
+
+        let key = DataKey::PrepayDiscount(plan_id, periods);
+        if discount_bps == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &discount_bps);
+        }
+    }
+
+    /// Turn a plan into a team plan sold per seat, up to `max_seats` per subscription
+    pub fn set_team_plan(env: Env, plan_id: u32, max_seats: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(plan_id))
+            .expect("Plan not found");
+
+        plan.max_seats = max_seats;
+
+        env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
+    }
+
+    /// Close a plan to new subscribers. Existing subscribers keep renewing at the price
+    /// version they are pinned to, and the plan's prices are frozen from now on. After
+    /// `sunset_date`, renewals move them to the successor plan.
+    pub fn deprecate_plan(
+        env: Env,
+        plan_id: u32,
+        sunset_date: Option<u64>,
+        successor_plan_id: Option<u32>,
+    ) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(plan_id))
+            .expect("Plan not found");
+
+        if sunset_date.is_some() && successor_plan_id.is_none() {
+            panic!("Sunset requires a successor plan");
+        }
+
+        if let Some(successor_plan_id) = successor_plan_id {
+            let successor: SubscriptionPlan = env
+                .storage()
+                .persistent()
+                .get(&DataKey::SubscriptionPlan(successor_plan_id))
+                .expect("Successor plan not found");
+
+            if successor_plan_id == plan_id || successor.deprecated || !successor.is_active {
+                panic!("Invalid successor plan");
+            }
+        }
+
+        plan.deprecated = true;
+        plan.sunset_date = sunset_date;
+        plan.successor_plan_id = successor_plan_id;
+
+        env.storage().persistent().set(&DataKey::SubscriptionPlan(plan_id), &plan);
+    }
+
+    /// Define a usage meter on a plan with included units and a per-unit overage price
+    pub fn set_meter_limit(
+        env: Env,
+        plan_id: u32,
+        meter_id: Symbol,
+        included_units: u64,
+        overage_price: i128,
+    ) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::SubscriptionPlan(plan_id)) {
+            panic!("Plan not found");
+        }
+        if overage_price < 0 {
+            panic!("Invalid overage price");
+        }
+
+        let mut meters = metering::plan_meters(&env, plan_id);
+        if !meters.contains(&meter_id) {
+            meters.push_back(meter_id.clone());
+            env.storage().persistent().set(&DataKey::PlanMeters(plan_id), &meters);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::MeterLimit(plan_id, meter_id),
+            &MeterLimit {
+                included_units,
+                overage_price,
+            },
+        );
+    }
+
+    /// Allow or revoke a consumer contract's right to record usage
+    pub fn set_metering_consumer(env: Env, consumer: Address, allowed: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if allowed {
+            env.storage().persistent().set(&DataKey::MeteringConsumer(consumer), &true);
+        } else {
+            env.storage().persistent().remove(&DataKey::MeteringConsumer(consumer));
+        }
+    }
+
+    /// Set the Stellar Asset Contract address wrapping native XLM on this network
+    pub fn set_native_token(env: Env, native_token: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::NativeToken, &native_token);
+    }
+
+    /// Accept native XLM for a plan at the given price (in stroops)
+    pub fn set_plan_native_price(env: Env, plan_id: u32, price: i128) {
+        let native_token = subscription::native_token(&env);
+        Self::set_plan_token_price(env, plan_id, native_token, price);
+    }
+
+    /// Configure the loyalty points token minted on renewals and redeemable for discounts
+    pub fn set_rewards_config(env: Env, token: Address, points_per_renewal: i128, point_value: i128) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if points_per_renewal < 0 || point_value < 0 {
+            panic!("Invalid rewards config");
+        }
+
+        env.storage().instance().set(
+            &DataKey::RewardsConfig,
+            &RewardsConfig {
+                token,
+                points_per_renewal,
+                point_value,
+            },
+        );
+    }
+
+    /// Set the analytics aggregator that receives protocol metrics
+    pub fn set_analytics(env: Env, analytics: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Analytics, &analytics);
+    }
+
+    /// Set the membership badge contract minted to subscribers; this contract must be a badge minter
+    pub fn set_membership_badge(env: Env, badge: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::MembershipBadge, &badge);
+    }
+
+    /// Set the governance parameter registry; registry values override local defaults
+    pub fn set_registry(env: Env, registry: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Registry, &registry);
+    }
+
+    /// Configure how many times and how often failed renewal charges are retried
+    pub fn set_dunning_config(env: Env, max_retries: u32, retry_interval_days: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if retry_interval_days == 0 {
+            panic!("Retry interval must be at least 1 day");
+        }
+
+        env.storage().instance().set(
+            &DataKey::DunningConfig,
+            &DunningConfig {
+                max_retries,
+                retry_interval_days,
+            },
+        );
+    }
+
+    /// Withdraw held subscription revenue
+    pub fn withdraw_revenue(env: Env, token: Address, amount: i128, to: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        subscription::debit_treasury(&env, &token, amount);
+
+        let token_client = token::TokenClient::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
+
+        events::emit_revenue_withdrawn(&env, events::RevenueWithdrawnEvent { token, amount, to });
+    }
+
     /// Subscribe to a plan
     pub fn subscribe(env: Env, user: Address, plan_id: u32) -> u64 {
+        let token = subscription::default_token(&env);
+        Self::subscribe_with_token(env, user, plan_id, token)
+    }
+
+    /// Subscribe to a plan, paying in one of the plan's accepted tokens
+    pub fn subscribe_with_token(env: Env, user: Address, plan_id: u32, token: Address) -> u64 {
+        Self::start_subscription(env, user, plan_id, token, 1, 1)
+    }
+
+    /// Subscribe using a referral code registered in the referral contract; the user's
+    /// referral chain earns a share of their payments as set by the referral config
+    pub fn subscribe_with_referral(env: Env, user: Address, plan_id: u32, code: Symbol) -> u64 {
+        if env.storage().persistent().has(&DataKey::UserSubscription(user.clone())) {
+            panic!("Referrals are for new subscribers");
+        }
+
+        referral::attribute(&env, &user, &code);
+        Self::subscribe(env, user, plan_id)
+    }
+
+    /// Subscribe and pay for `periods` terms upfront at the plan's prepay discount
+    pub fn subscribe_prepaid(env: Env, user: Address, plan_id: u32, periods: u32) -> u64 {
+        let token = subscription::default_token(&env);
+        Self::start_subscription(env, user, plan_id, token, 1, periods)
+    }
+
+    /// Buy `seats` seats on a team plan; the owner is charged the plan price per seat
+    pub fn subscribe_team(env: Env, owner: Address, plan_id: u32, seats: u32) -> u64 {
+        let token = subscription::default_token(&env);
+        Self::start_subscription(env, owner, plan_id, token, seats, 1)
+    }
+
+    fn start_subscription(
+        env: Env,
+        user: Address,
+        plan_id: u32,
+        token: Address,
+        seats: u32,
+        periods: u32,
+    ) -> u64 {
         user.require_auth();
+        subscription::require_not_suspended(&env, &user);
 
         let plan: SubscriptionPlan = env
             .storage()
@@ -102,22 +639,36 @@ impl SubscriptionContract {
             panic!("Plan is not active");
         }
 
-        // Check if user already has an active subscription
-        if let Some(existing_sub) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user.clone()))
-        {
-            if existing_sub.status == SubscriptionStatus::Active {
-                panic!("User already has an active subscription");
-            }
+        if plan.deprecated {
+            panic!("Plan is deprecated");
         }
 
-        subscription::process_subscription_payment(&env, &user, &plan);
+        if plan.max_seats == 0 && seats != 1 {
+            panic!("Plan is not a team plan");
+        }
 
-        let subscription_id: u64 = env.storage().instance().get(&DataKey::NextSubscriptionId).unwrap();
+        if seats == 0 || (plan.max_seats > 0 && seats > plan.max_seats) {
+            panic!("Invalid seat count");
+        }
+
+        if periods == 0 {
+            panic!("Invalid number of periods");
+        }
+
+        Self::replace_previous(&env, &user);
+
+        let price = subscription::prepaid_price(
+            &env,
+            plan_id,
+            subscription::plan_price(&env, &plan, &token) * seats as i128,
+            periods,
+        );
         let current_time = env.ledger().timestamp();
-        let end_date = current_time + (plan.duration_days as u64 * 86400);
+        subscription::process_payment(&env, &user, plan_id, &token, price);
+        referral::reward(&env, &user, &plan, current_time, &token, price);
+
+        let subscription_id: u64 = env.storage().instance().get(&DataKey::NextSubscriptionId).unwrap();
+        let end_date = current_time + (plan.duration_days as u64 * 86400 * periods as u64);
 
         let subscription = UserSubscription {
             subscription_id,
@@ -130,63 +681,351 @@ impl SubscriptionContract {
             auto_renew: true,
             is_family_plan: false,
             family_members: Vec::new(&env),
+            payment_token: token,
+            seats,
+            seat_members: Vec::new(&env),
+            price_version: plan.price_version,
+            paid_amount: price,
+            paid_from: current_time,
         };
 
         env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
         env.storage().instance().set(&DataKey::NextSubscriptionId, &(subscription_id + 1));
+        analytics::report(&env, analytics::ACTIVE_SUBSCRIPTIONS, 1);
+        stats::record(&env, subscription.plan_id, |s| s.subscriptions += 1);
+        subscribers::add(&env, &user, subscription.plan_id);
+        membership::sync(&env, &subscription);
 
         events::emit_subscription_created(
             &env,
             events::SubscriptionCreatedEvent {
                 subscription_id,
                 user,
-                plan_id,
-                end_date,
+                plan_id,
+                end_date,
+            },
+        );
+
+        subscription_id
+    }
+
+    /// Clear the user's previous subscription before a new one takes its place. A paused or
+    /// grace-period subscription still counts as active, so it stops counting here.
+    fn replace_previous(env: &Env, user: &Address) {
+        if let Some(existing_sub) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user.clone()))
+        {
+            match existing_sub.status {
+                SubscriptionStatus::Active | SubscriptionStatus::PastDue => {
+                    panic!("User already has an active subscription");
+                }
+                SubscriptionStatus::GracePeriod | SubscriptionStatus::Paused => {
+                    analytics::report(env, analytics::ACTIVE_SUBSCRIPTIONS, -1);
+                }
+                SubscriptionStatus::Expired | SubscriptionStatus::Cancelled => {}
+            }
+
+            subscribers::remove(env, user, existing_sub.plan_id);
+            env.storage().persistent().remove(&DataKey::PausedSubscription(user.clone()));
+        }
+    }
+
+    /// Subscribe to a plan, paying in native XLM
+    pub fn subscribe_with_native(env: Env, user: Address, plan_id: u32) -> u64 {
+        let native_token = subscription::native_token(&env);
+        Self::subscribe_with_token(env, user, plan_id, native_token)
+    }
+
+    /// Renew subscription, paid by the user. Keepers renew from an allowance with `process_renewal`.
+    pub fn renew_subscription(env: Env, user: Address) {
+        user.require_auth();
+        Self::renew(env, user, None, 0, 1);
+    }
+
+    /// Renew subscription, paying part of the price with loyalty points
+    pub fn renew_with_points(env: Env, user: Address, points: i128) {
+        user.require_auth();
+        Self::renew(env, user, None, points, 1);
+    }
+
+    /// Renew subscription, switching payment to another accepted token
+    pub fn renew_with_token(env: Env, user: Address, token: Address) {
+        user.require_auth();
+        Self::renew(env, user, Some(token), 0, 1);
+    }
+
+    /// Renew for `periods` terms upfront at the plan's prepay discount
+    pub fn renew_prepaid(env: Env, user: Address, periods: u32) {
+        user.require_auth();
+
+        if periods == 0 {
+            panic!("Invalid number of periods");
+        }
+
+        Self::renew(env, user, None, 0, periods);
+    }
+
+    fn renew(env: Env, user: Address, token: Option<Address>, points: i128, periods: u32) {
+        let mut subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(user.clone()))
+            .expect("Subscription not found");
+
+        subscription::require_not_suspended(&env, &user);
+
+        if subscription.status == SubscriptionStatus::Cancelled {
+            panic!("Cannot renew cancelled subscription");
+        }
+
+        // Usage is settled and reset under the plan it was recorded on, before any
+        // scheduled change or sunset migration swaps the plan
+        let current_plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(subscription.plan_id))
+            .expect("Plan not found");
+        let overage = metering::overage_amount(&env, &subscription, &current_plan);
+
+        // Price the renewal before migrating, while the subscription is still on its pinned version
+        let plan = subscription::peek_renewal_plan(&env, &subscription);
+        let token = token.unwrap_or(subscription.payment_token.clone());
+        let price = subscription::prepaid_price(
+            &env,
+            plan.plan_id,
+            subscription::subscription_price(&env, &subscription, &plan, &token),
+            periods,
+        );
+        let price_version = subscription::renewal_price_version(&env, &subscription, &plan, &token);
+
+        let plan = subscription::renewal_plan(&env, &mut subscription);
+        let price = loyalty::apply_discount(&env, &subscription, price);
+        let discount = rewards::redeem_points(&env, &user, points, price);
+        let amount_paid = price - discount + overage;
+        subscription::process_payment(&env, &user, plan.plan_id, &token, amount_paid);
+        referral::reward(&env, &user, &plan, subscription.end_date, &token, amount_paid);
+        rewards::award_renewal_points(&env, &user);
+        metering::reset_usage(&env, &subscription, &current_plan);
+        loyalty::record_renewal(&env, &subscription);
+
+        if subscription.status == SubscriptionStatus::Expired {
+            analytics::report(&env, analytics::ACTIVE_SUBSCRIPTIONS, 1);
+            subscribers::add(&env, &user, subscription.plan_id);
+        }
+
+        let current_time = env.ledger().timestamp();
+        subscription.end_date = current_time + (plan.duration_days as u64 * 86400 * periods as u64);
+        subscription.last_payment_date = current_time;
+        subscription.status = SubscriptionStatus::Active;
+        subscription.payment_token = token;
+        subscription.price_version = price_version;
+        subscription.paid_amount = price - discount;
+        subscription.paid_from = current_time;
+
+        env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
+        env.storage().persistent().remove(&DataKey::Dunning(user.clone()));
+        env.storage().persistent().remove(&DataKey::PauseCount(subscription.subscription_id));
+        membership::sync(&env, &subscription);
+
+        events::emit_subscription_renewed(
+            &env,
+            events::SubscriptionRenewedEvent {
+                subscription_id: subscription.subscription_id,
+                user,
+                new_end_date: subscription.end_date,
+                amount_paid,
             },
         );
-
-        subscription_id
     }
 
-    /// Renew subscription (can be called manually or automatically)
-    pub fn renew_subscription(env: Env, user: Address) {
-        let mut subscription: UserSubscription = env
+    /// Charge a due auto-renewing subscription from the user's token allowance.
+    /// Anyone may call this; returns false and emits `renewal_failed` if the charge fails.
+    pub fn process_renewal(env: Env, user: Address) -> bool {
+        let subscription: UserSubscription = env
             .storage()
             .persistent()
             .get(&DataKey::UserSubscription(user.clone()))
             .expect("Subscription not found");
 
-        if subscription.status == SubscriptionStatus::Cancelled {
-            panic!("Cannot renew cancelled subscription");
+        subscription::require_not_suspended(&env, &user);
+
+        if !subscription::is_payment_due(&env, &subscription) {
+            panic!("Renewal not due");
         }
 
-        let plan: SubscriptionPlan = env
+        Self::auto_renew(&env, subscription)
+    }
+
+    /// Process renewals for a batch of users, skipping any that are not due.
+    /// Returns the number of subscriptions renewed.
+    pub fn process_due_renewals(env: Env, users: Vec<Address>) -> u32 {
+        let mut renewed = 0;
+
+        for user in users.iter() {
+            if let Some(subscription) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user))
+            {
+                if subscription::is_payment_due(&env, &subscription)
+                    && Self::auto_renew(&env, subscription)
+                {
+                    renewed += 1;
+                }
+            }
+        }
+
+        renewed
+    }
+
+    fn auto_renew(env: &Env, mut subscription: UserSubscription) -> bool {
+        let current_plan: SubscriptionPlan = env
             .storage()
             .persistent()
             .get(&DataKey::SubscriptionPlan(subscription.plan_id))
             .expect("Plan not found");
+        let plan = subscription::peek_renewal_plan(env, &subscription);
+
+        let user = subscription.user.clone();
+        let amount_due = subscription::renewal_amount(env, &subscription, &plan);
+        let overage = metering::overage_amount(env, &subscription, &current_plan);
+        let price_version =
+            subscription::renewal_price_version(env, &subscription, &plan, &subscription.payment_token);
 
-        subscription::process_subscription_payment(&env, &user, &plan);
+        if !subscription::collect_renewal_payment(env, &user, plan.plan_id, &subscription.payment_token, amount_due) {
+            Self::record_failed_renewal(env, subscription, amount_due);
+            return false;
+        }
+
+        // Apply any scheduled change or sunset migration only once the renewal is paid
+        let plan = subscription::renewal_plan(env, &mut subscription);
+        referral::reward(env, &user, &plan, subscription.end_date, &subscription.payment_token, amount_due);
+        rewards::award_renewal_points(env, &user);
+        metering::reset_usage(env, &subscription, &current_plan);
+        loyalty::record_renewal(env, &subscription);
+        env.storage().persistent().remove(&DataKey::Dunning(user.clone()));
+        env.storage().persistent().remove(&DataKey::PauseCount(subscription.subscription_id));
 
+        // Extend from the current end date so renewing early loses no paid time
         let current_time = env.ledger().timestamp();
-        subscription.end_date = current_time + (plan.duration_days as u64 * 86400);
+        subscription.paid_amount = subscription::credit_at(&subscription, current_time) + amount_due - overage;
+        subscription.paid_from = current_time;
+        subscription.end_date =
+            subscription.end_date.max(current_time) + (plan.duration_days as u64 * 86400);
         subscription.last_payment_date = current_time;
         subscription.status = SubscriptionStatus::Active;
+        subscription.price_version = price_version;
 
         env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
+        membership::sync(env, &subscription);
 
         events::emit_subscription_renewed(
-            &env,
+            env,
             events::SubscriptionRenewedEvent {
                 subscription_id: subscription.subscription_id,
                 user,
                 new_end_date: subscription.end_date,
-                amount_paid: plan.price,
+                amount_paid: amount_due,
+            },
+        );
+
+        true
+    }
+
+    /// Mark the subscription past due and schedule a retry, or expire it once
+    /// retries are exhausted or the next retry would fall outside the grace period
+    fn record_failed_renewal(env: &Env, mut subscription: UserSubscription, amount_due: i128) {
+        let user = subscription.user.clone();
+        let config = subscription::dunning_config(env);
+        let failed_attempts = subscription::dunning_state(env, &user)
+            .map(|state| state.failed_attempts)
+            .unwrap_or(0)
+            + 1;
+        let next_retry_at =
+            env.ledger().timestamp() + (config.retry_interval_days as u64 * 86400);
+
+        let next_retry_at = if failed_attempts > config.max_retries
+            || next_retry_at > subscription::grace_period_end(env, &subscription)
+        {
+            subscription.status = SubscriptionStatus::Expired;
+            subscription.auto_renew = false;
+            env.storage().persistent().remove(&DataKey::Dunning(user.clone()));
+            analytics::report(env, analytics::ACTIVE_SUBSCRIPTIONS, -1);
+            stats::record(env, subscription.plan_id, |s| s.expirations += 1);
+            subscribers::remove(env, &user, subscription.plan_id);
+            None
+        } else {
+            subscription.status = SubscriptionStatus::PastDue;
+            env.storage().persistent().set(
+                &DataKey::Dunning(user.clone()),
+                &DunningState {
+                    failed_attempts,
+                    next_retry_at,
+                },
+            );
+            Some(next_retry_at)
+        };
+
+        env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
+
+        events::emit_renewal_failed(
+            env,
+            events::RenewalFailedEvent {
+                subscription_id: subscription.subscription_id,
+                user,
+                amount_due,
+                attempt: failed_attempts,
+                next_retry_at,
+            },
+        );
+    }
+
+    /// Record metered usage for a subscriber. Usage beyond the plan's included units
+    /// is billed at the next renewal. Returns the total for the current period.
+    pub fn record_usage(env: Env, consumer: Address, user: Address, meter_id: Symbol, units: u64) -> u64 {
+        consumer.require_auth();
+
+        if !metering::is_consumer(&env, &consumer) {
+            panic!("Not an authorized consumer");
+        }
+
+        let subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(user.clone()))
+            .expect("Subscription not found");
+
+        if subscription.status != SubscriptionStatus::Active {
+            panic!("Subscription is not active");
+        }
+
+        if !metering::plan_meters(&env, subscription.plan_id).contains(&meter_id) {
+            panic!("Meter not defined for plan");
+        }
+
+        let total = metering::usage(&env, subscription.subscription_id, &meter_id) + units;
+        env.storage().persistent().set(
+            &DataKey::Usage(subscription.subscription_id, meter_id.clone()),
+            &total,
+        );
+
+        events::emit_usage_recorded(
+            &env,
+            events::UsageRecordedEvent {
+                subscription_id: subscription.subscription_id,
+                user,
+                meter_id,
+                units,
+                total,
             },
         );
+
+        total
     }
 
-    /// Cancel subscription with prorated refund
+    /// Cancel subscription, refunding according to the plan's refund policy
     pub fn cancel_subscription(env: Env, user: Address) {
         user.require_auth();
 
@@ -196,6 +1035,8 @@ impl SubscriptionContract {
             .get(&DataKey::UserSubscription(user.clone()))
             .expect("Subscription not found");
 
+        subscription::require_not_suspended(&env, &user);
+
         if subscription.status == SubscriptionStatus::Cancelled {
             panic!("Subscription already cancelled");
         }
@@ -206,20 +1047,24 @@ impl SubscriptionContract {
             .get(&DataKey::SubscriptionPlan(subscription.plan_id))
             .expect("Plan not found");
 
-        let refund_amount = subscription::calculate_prorated_refund(
-            &env,
-            &subscription,
-            &plan,
-        );
+        let refund_amount = subscription::calculate_refund(&env, &subscription, &plan);
 
         if refund_amount > 0 {
-            subscription::process_refund(&env, &user, refund_amount);
+            subscription::process_refund(&env, &user, plan.plan_id, &subscription.payment_token, refund_amount);
+        }
+
+        if subscription.status != SubscriptionStatus::Expired {
+            analytics::report(&env, analytics::ACTIVE_SUBSCRIPTIONS, -1);
+            stats::record(&env, subscription.plan_id, |s| s.cancellations += 1);
         }
+        subscribers::remove(&env, &user, subscription.plan_id);
 
         subscription.status = SubscriptionStatus::Cancelled;
         subscription.auto_renew = false;
 
         env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
+        env.storage().persistent().remove(&DataKey::Dunning(user.clone()));
+        membership::revoke(&env, &user);
 
         events::emit_subscription_cancelled(
             &env,
@@ -234,6 +1079,7 @@ impl SubscriptionContract {
     /// Pause subscription
     pub fn pause_subscription(env: Env, user: Address) {
         user.require_auth();
+        subscription::require_not_suspended(&env, &user);
 
         let mut subscription: UserSubscription = env
             .storage()
@@ -245,6 +1091,22 @@ impl SubscriptionContract {
             panic!("Can only pause active subscriptions");
         }
 
+        let plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(subscription.plan_id))
+            .expect("Plan not found");
+
+        let pause_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PauseCount(subscription.subscription_id))
+            .unwrap_or(0);
+
+        if plan.max_pauses_per_term > 0 && pause_count >= plan.max_pauses_per_term {
+            panic!("Pause limit reached for this term");
+        }
+
         let current_time = env.ledger().timestamp();
         let remaining_days = ((subscription.end_date - current_time) / 86400) as u32;
 
@@ -258,6 +1120,10 @@ impl SubscriptionContract {
 
         env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
         env.storage().persistent().set(&DataKey::PausedSubscription(user.clone()), &paused_data);
+        env.storage().persistent().set(
+            &DataKey::PauseCount(subscription.subscription_id),
+            &(pause_count + 1),
+        );
 
         events::emit_subscription_paused(
             &env,
@@ -272,8 +1138,9 @@ impl SubscriptionContract {
     /// Resume paused subscription
     pub fn resume_subscription(env: Env, user: Address) {
         user.require_auth();
+        subscription::require_not_suspended(&env, &user);
 
-        let mut subscription: UserSubscription = env
+        let subscription: UserSubscription = env
             .storage()
             .persistent()
             .get(&DataKey::UserSubscription(user.clone()))
@@ -283,30 +1150,107 @@ impl SubscriptionContract {
             panic!("Subscription is not paused");
         }
 
+        Self::end_pause(&env, subscription);
+    }
+
+    /// Restart the clock on a paused subscription. A pause that ran past the plan's
+    /// maximum is treated as having ended when the maximum was reached.
+    fn end_pause(env: &Env, mut subscription: UserSubscription) -> UserSubscription {
+        let user = subscription.user.clone();
         let paused_data: PausedSubscriptionData = env
             .storage()
             .persistent()
             .get(&DataKey::PausedSubscription(user.clone()))
             .expect("Paused data not found");
 
-        let current_time = env.ledger().timestamp();
-        let new_end_date = current_time + (paused_data.remaining_days as u64 * 86400);
+        let resumed_at = match subscription::pause_deadline(env, &subscription, &paused_data) {
+            Some(deadline) => deadline.min(env.ledger().timestamp()),
+            None => env.ledger().timestamp(),
+        };
+        let new_end_date = resumed_at + (paused_data.remaining_days as u64 * 86400);
 
+        // Credit left when the pause began now covers the rescheduled remainder of the term
+        subscription.paid_amount = subscription::credit_at(&subscription, paused_data.paused_at);
+        subscription.paid_from = resumed_at;
         subscription.status = SubscriptionStatus::Active;
         subscription.end_date = new_end_date;
         subscription.auto_renew = true;
 
         env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
         env.storage().persistent().remove(&DataKey::PausedSubscription(user.clone()));
+        membership::sync(env, &subscription);
 
         events::emit_subscription_resumed(
-            &env,
+            env,
             events::SubscriptionResumedEvent {
                 subscription_id: subscription.subscription_id,
                 user,
                 new_end_date,
             },
         );
+
+        subscription
+    }
+
+    /// Put a subscription on hold, e.g. for fraud or a chargeback. A suspended
+    /// subscription keeps its record but grants no access and cannot renew.
+    pub fn suspend_subscription(env: Env, user: Address, reason: String) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        // Users who only hold a family slot or a seat can be suspended too
+        let subscription_id = env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user.clone()))
+            .map(|subscription| subscription.subscription_id);
+
+        if subscription::is_suspended(&env, &user) {
+            panic!("Subscription already suspended");
+        }
+
+        env.storage().persistent().set(
+            &DataKey::Suspension(user.clone()),
+            &Suspension {
+                reason: reason.clone(),
+                suspended_at: env.ledger().timestamp(),
+            },
+        );
+
+        events::emit_subscription_suspended(
+            &env,
+            events::SubscriptionSuspendedEvent {
+                subscription_id,
+                user,
+                reason,
+            },
+        );
+    }
+
+    /// Lift an admin hold
+    pub fn unsuspend_subscription(env: Env, user: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let subscription_id = env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user.clone()))
+            .map(|subscription| subscription.subscription_id);
+
+        if !subscription::is_suspended(&env, &user) {
+            panic!("Subscription is not suspended");
+        }
+
+        env.storage().persistent().remove(&DataKey::Suspension(user.clone()));
+
+        events::emit_subscription_unsuspended(
+            &env,
+            events::SubscriptionUnsuspendedEvent {
+                subscription_id,
+                user,
+            },
+        );
     }
 
     /// Upgrade or downgrade subscription
@@ -319,15 +1263,90 @@ impl SubscriptionContract {
             .get(&DataKey::UserSubscription(user.clone()))
             .expect("Subscription not found");
 
+        subscription::require_not_suspended(&env, &user);
+
         if subscription.status != SubscriptionStatus::Active {
             panic!("Can only change active subscriptions");
         }
 
-        let old_plan: SubscriptionPlan = env
+        let old_plan_id = subscription.plan_id;
+        let new_plan: SubscriptionPlan = env
             .storage()
             .persistent()
-            .get(&DataKey::SubscriptionPlan(subscription.plan_id))
-            .expect("Old plan not found");
+            .get(&DataKey::SubscriptionPlan(new_plan_id))
+            .expect("New plan not found");
+
+        if !new_plan.is_active || new_plan.deprecated {
+            panic!("New plan is not active");
+        }
+
+        if subscription.seats > 1 && subscription.seats > new_plan.max_seats {
+            panic!("New plan does not support this many seats");
+        }
+
+        let credit = subscription::remaining_credit(&env, &subscription);
+        let prorated_amount = subscription::calculate_plan_change_amount(&env, &subscription, &new_plan);
+
+        if prorated_amount > 0 {
+            // Upgrade - charge difference
+            subscription::process_payment(&env, &user, new_plan_id, &subscription.payment_token, prorated_amount);
+        } else if prorated_amount < 0 {
+            // Downgrade - refund difference against the plan being left
+            subscription::process_refund(&env, &user, old_plan_id, &subscription.payment_token, -prorated_amount);
+        }
+
+        // The difference covers the rest of the current term; a term that has already
+        // run out was charged the full new price and starts over
+        let current_time = env.ledger().timestamp();
+        if current_time >= subscription.end_date {
+            subscription.start_date = current_time;
+            subscription.end_date = current_time + (new_plan.duration_days as u64 * 86400);
+            subscription.last_payment_date = current_time;
+        }
+        subscription.paid_amount = credit + prorated_amount;
+        subscription.paid_from = current_time;
+
+        subscription.plan_id = new_plan_id;
+        subscription.price_version = new_plan.price_version;
+        subscribers::move_plan(&env, &user, old_plan_id, new_plan_id);
+
+        // An immediate change supersedes any change scheduled for the next renewal
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ScheduledPlanChange(subscription.subscription_id));
+        env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
+
+        events::emit_subscription_upgraded(
+            &env,
+            events::SubscriptionUpgradedEvent {
+                subscription_id: subscription.subscription_id,
+                user,
+                old_plan_id,
+                new_plan_id,
+                prorated_amount,
+            },
+        );
+    }
+
+    /// Switch plans at the end of the current period instead of immediately.
+    /// The change is applied when the subscription next renews.
+    pub fn schedule_plan_change(env: Env, user: Address, new_plan_id: u32) {
+        user.require_auth();
+        subscription::require_not_suspended(&env, &user);
+
+        let subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(user.clone()))
+            .expect("Subscription not found");
+
+        if subscription.status != SubscriptionStatus::Active {
+            panic!("Can only change active subscriptions");
+        }
+
+        if new_plan_id == subscription.plan_id {
+            panic!("Already on this plan");
+        }
 
         let new_plan: SubscriptionPlan = env
             .storage()
@@ -335,45 +1354,133 @@ impl SubscriptionContract {
             .get(&DataKey::SubscriptionPlan(new_plan_id))
             .expect("New plan not found");
 
-        if !new_plan.is_active {
+        if !new_plan.is_active || new_plan.deprecated {
             panic!("New plan is not active");
         }
 
-        let prorated_amount = subscription::calculate_plan_change_amount(
+        if subscription.seats > 1 && subscription.seats > new_plan.max_seats {
+            panic!("New plan does not support this many seats");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ScheduledPlanChange(subscription.subscription_id), &new_plan_id);
+    }
+
+    /// Drop a scheduled plan change
+    pub fn cancel_scheduled_plan_change(env: Env, user: Address) {
+        user.require_auth();
+        subscription::require_not_suspended(&env, &user);
+
+        let subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(user))
+            .expect("Subscription not found");
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ScheduledPlanChange(subscription.subscription_id));
+    }
+
+    /// Add family member to subscription
+    pub fn add_family_member(env: Env, owner: Address, member: Address) {
+        owner.require_auth();
+        subscription::require_not_suspended(&env, &owner);
+
+        let mut subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(owner.clone()))
+            .expect("Subscription not found");
+
+        if subscription.status != SubscriptionStatus::Active {
+            panic!("Subscription must be active");
+        }
+
+        let plan: SubscriptionPlan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(subscription.plan_id))
+            .expect("Plan not found");
+
+        if subscription.family_members.len() >= plan.max_family_members {
+            panic!("Maximum family members reached");
+        }
+
+        if subscription.family_members.contains(&member) {
+            panic!("Member already added");
+        }
+
+        if member == owner {
+            panic!("Owner cannot be a family member");
+        }
+
+        // A member can share one subscription at a time; stale entries from plans
+        // that no longer list them are overwritten
+        if let Some(current_owner) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::FamilyMemberOf(member.clone()))
+        {
+            if let Some(current) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(current_owner))
+            {
+                if current.family_members.contains(&member) {
+                    panic!("Member already in a family plan");
+                }
+            }
+        }
+
+        subscription.family_members.push_back(member.clone());
+        subscription.is_family_plan = true;
+
+        env.storage().persistent().set(&DataKey::UserSubscription(owner.clone()), &subscription);
+        env.storage().persistent().set(&DataKey::FamilyMemberOf(member.clone()), &owner);
+
+        events::emit_family_member_added(
             &env,
-            &subscription,
-            &old_plan,
-            &new_plan,
+            events::FamilyMemberAddedEvent {
+                subscription_id: subscription.subscription_id,
+                owner,
+                member,
+            },
         );
+    }
 
-        if prorated_amount > 0 {
-            // Upgrade - charge difference
-            subscription::process_subscription_payment(&env, &user, &new_plan);
-        } else if prorated_amount < 0 {
-            // Downgrade - refund difference
-            subscription::process_refund(&env, &user, -prorated_amount);
-        }
+    /// Remove family member from subscription
+    pub fn remove_family_member(env: Env, owner: Address, member: Address) {
+        owner.require_auth();
+        subscription::require_not_suspended(&env, &owner);
+
+        let mut subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(owner.clone()))
+            .expect("Subscription not found");
+
+        let member_index = subscription
+            .family_members
+            .iter()
+            .position(|m| m == member)
+            .expect("Member not found");
 
-        let old_plan_id = subscription.plan_id;
-        subscription.plan_id = new_plan_id;
+        subscription.family_members.remove(member_index as u32);
 
-        env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
+        if subscription.family_members.is_empty() {
+            subscription.is_family_plan = false;
+        }
 
-        events::emit_subscription_upgraded(
-            &env,
-            events::SubscriptionUpgradedEvent {
-                subscription_id: subscription.subscription_id,
-                user,
-                old_plan_id,
-                new_plan_id,
-                prorated_amount,
-            },
-        );
+        env.storage().persistent().set(&DataKey::UserSubscription(owner), &subscription);
+        env.storage().persistent().remove(&DataKey::FamilyMemberOf(member));
     }
 
-    /// Add family member to subscription
-    pub fn add_family_member(env: Env, owner: Address, member: Address) {
+    /// Assign one of a team subscription's seats to a member
+    pub fn assign_seat(env: Env, owner: Address, member: Address) {
         owner.require_auth();
+        subscription::require_not_suspended(&env, &owner);
 
         let mut subscription: UserSubscription = env
             .storage()
@@ -391,22 +1498,47 @@ impl SubscriptionContract {
             .get(&DataKey::SubscriptionPlan(subscription.plan_id))
             .expect("Plan not found");
 
-        if subscription.family_members.len() >= plan.max_family_members {
-            panic!("Maximum family members reached");
+        if plan.max_seats == 0 {
+            panic!("Plan is not a team plan");
         }
 
-        if subscription.family_members.contains(&member) {
-            panic!("Member already added");
+        if subscription.seat_members.len() >= subscription.seats {
+            panic!("No free seats");
         }
 
-        subscription.family_members.push_back(member.clone());
-        subscription.is_family_plan = true;
+        if subscription.seat_members.contains(&member) {
+            panic!("Seat already assigned");
+        }
+
+        if member == owner {
+            panic!("Owner cannot hold a seat");
+        }
+
+        // A member holds one seat at a time; stale entries are overwritten
+        if let Some(current_owner) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::SeatHolderOf(member.clone()))
+        {
+            if let Some(current) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(current_owner))
+            {
+                if current.seat_members.contains(&member) {
+                    panic!("Member already holds a seat");
+                }
+            }
+        }
+
+        subscription.seat_members.push_back(member.clone());
 
         env.storage().persistent().set(&DataKey::UserSubscription(owner.clone()), &subscription);
+        env.storage().persistent().set(&DataKey::SeatHolderOf(member.clone()), &owner);
 
-        events::emit_family_member_added(
+        events::emit_seat_assigned(
             &env,
-            events::FamilyMemberAddedEvent {
+            events::SeatAssignedEvent {
                 subscription_id: subscription.subscription_id,
                 owner,
                 member,
@@ -414,9 +1546,10 @@ impl SubscriptionContract {
         );
     }
 
-    /// Remove family member from subscription
-    pub fn remove_family_member(env: Env, owner: Address, member: Address) {
+    /// Free a seat so it can be assigned to someone else
+    pub fn unassign_seat(env: Env, owner: Address, member: Address) {
         owner.require_auth();
+        subscription::require_not_suspended(&env, &owner);
 
         let mut subscription: UserSubscription = env
             .storage()
@@ -425,22 +1558,134 @@ impl SubscriptionContract {
             .expect("Subscription not found");
 
         let member_index = subscription
-            .family_members
+            .seat_members
             .iter()
             .position(|m| m == member)
-            .expect("Member not found");
+            .expect("Seat not assigned");
 
-        subscription.family_members.remove(member_index as u32);
+        subscription.seat_members.remove(member_index as u32);
 
-        if subscription.family_members.is_empty() {
-            subscription.is_family_plan = false;
+        env.storage().persistent().set(&DataKey::UserSubscription(owner), &subscription);
+        env.storage().persistent().remove(&DataKey::SeatHolderOf(member));
+    }
+
+    /// Propose moving the subscription to a new address; the new owner must accept
+    pub fn initiate_transfer(env: Env, user: Address, new_owner: Address) {
+        user.require_auth();
+
+        let subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(user.clone()))
+            .expect("Subscription not found");
+
+        subscription::require_not_suspended(&env, &user);
+        subscription::require_transferable(&subscription);
+
+        if new_owner == user {
+            panic!("Cannot transfer to self");
         }
 
-        env.storage().persistent().set(&DataKey::UserSubscription(owner), &subscription);
+        env.storage().persistent().set(&DataKey::PendingTransfer(user), &new_owner);
+    }
+
+    /// Withdraw a pending transfer
+    pub fn cancel_transfer(env: Env, user: Address) {
+        user.require_auth();
+        subscription::require_not_suspended(&env, &user);
+
+        if !env.storage().persistent().has(&DataKey::PendingTransfer(user.clone())) {
+            panic!("No pending transfer");
+        }
+
+        env.storage().persistent().remove(&DataKey::PendingTransfer(user));
+    }
+
+    /// Accept a subscription transfer from `from`. The record, family members, and any
+    /// pause state move to the new owner and the old address loses access. Referral rewards
+    /// on later payments follow the new owner's own referral attribution.
+    pub fn accept_transfer(env: Env, new_owner: Address, from: Address) {
+        new_owner.require_auth();
+
+        let pending: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingTransfer(from.clone()))
+            .expect("No pending transfer");
+
+        if pending != new_owner {
+            panic!("Transfer not for this address");
+        }
+
+        if env.storage().persistent().has(&DataKey::UserSubscription(new_owner.clone())) {
+            panic!("New owner already has a subscription");
+        }
+
+        let mut subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(from.clone()))
+            .expect("Subscription not found");
+
+        subscription::require_not_suspended(&env, &from);
+        subscription::require_not_suspended(&env, &new_owner);
+        subscription::require_transferable(&subscription);
+
+        subscription.user = new_owner.clone();
+
+        env.storage().persistent().set(&DataKey::UserSubscription(new_owner.clone()), &subscription);
+        env.storage().persistent().remove(&DataKey::UserSubscription(from.clone()));
+        env.storage().persistent().remove(&DataKey::PendingTransfer(from.clone()));
+
+        if let Some(paused_data) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PausedSubscriptionData>(&DataKey::PausedSubscription(from.clone()))
+        {
+            env.storage().persistent().set(&DataKey::PausedSubscription(new_owner.clone()), &paused_data);
+            env.storage().persistent().remove(&DataKey::PausedSubscription(from.clone()));
+        }
+
+        for member in subscription.family_members.iter() {
+            env.storage().persistent().set(&DataKey::FamilyMemberOf(member), &new_owner);
+        }
+
+        for member in subscription.seat_members.iter() {
+            env.storage().persistent().set(&DataKey::SeatHolderOf(member), &new_owner);
+        }
+
+        if subscribers::remove(&env, &from, subscription.plan_id) {
+            subscribers::add(&env, &new_owner, subscription.plan_id);
+        }
+
+        membership::revoke(&env, &from);
+        membership::sync(&env, &subscription);
+
+        events::emit_subscription_transferred(
+            &env,
+            events::SubscriptionTransferredEvent {
+                subscription_id: subscription.subscription_id,
+                from,
+                to: new_owner,
+            },
+        );
+    }
+
+    /// Get the address a pending transfer was offered to
+    pub fn get_pending_transfer(env: Env, user: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::PendingTransfer(user))
     }
 
-    /// Gift a subscription to another user
-    pub fn gift_subscription(env: Env, from: Address, to: Address, plan_id: u32) -> u64 {
+    /// Gift a subscription to another user with a message. The recipient can claim it
+    /// from `deliverable_at` onwards, so gifts can be timed for a birthday or event.
+    pub fn gift_subscription(
+        env: Env,
+        from: Address,
+        to: Address,
+        plan_id: u32,
+        message: String,
+        deliverable_at: u64,
+    ) -> u64 {
         from.require_auth();
 
         let plan: SubscriptionPlan = env
@@ -453,9 +1698,15 @@ impl SubscriptionContract {
             panic!("Plan is not active");
         }
 
+        if plan.deprecated {
+            panic!("Plan is deprecated");
+        }
+
         subscription::process_subscription_payment(&env, &from, &plan);
 
-        let gift_id: u64 = env.ledger().timestamp();
+        let gift_id: u64 = env.storage().instance().get(&DataKey::NextGiftId).unwrap_or(1);
+        env.storage().instance().set(&DataKey::NextGiftId, &(gift_id + 1));
+
         let gift = GiftSubscription {
             gift_id,
             from: from.clone(),
@@ -463,6 +1714,9 @@ impl SubscriptionContract {
             plan_id,
             claimed: false,
             created_at: env.ledger().timestamp(),
+            message,
+            deliverable_at,
+            announced: false,
         };
 
         env.storage().persistent().set(&DataKey::GiftedSubscription(gift_id), &gift);
@@ -477,12 +1731,49 @@ impl SubscriptionContract {
             },
         );
 
+        if deliverable_at <= env.ledger().timestamp() {
+            Self::announce_gift(env, gift_id);
+        }
+
         gift_id
     }
 
+    /// Emit `gift_deliverable` once a scheduled gift's delivery time has arrived.
+    /// Anyone may call this; it emits at most once per gift.
+    pub fn announce_gift(env: Env, gift_id: u64) {
+        let mut gift: GiftSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GiftedSubscription(gift_id))
+            .expect("Gift not found");
+
+        if env.ledger().timestamp() < gift.deliverable_at {
+            panic!("Gift not yet deliverable");
+        }
+
+        if gift.announced {
+            panic!("Gift already announced");
+        }
+
+        gift.announced = true;
+        env.storage().persistent().set(&DataKey::GiftedSubscription(gift_id), &gift);
+
+        events::emit_gift_deliverable(
+            &env,
+            events::GiftDeliverableEvent {
+                gift_id,
+                from: gift.from,
+                to: gift.to,
+                plan_id: gift.plan_id,
+                message: gift.message,
+            },
+        );
+    }
+
     /// Claim a gifted subscription
     pub fn claim_gift(env: Env, user: Address, gift_id: u64) -> u64 {
         user.require_auth();
+        subscription::require_not_suspended(&env, &user);
 
         let mut gift: GiftSubscription = env
             .storage()
@@ -498,17 +1789,12 @@ impl SubscriptionContract {
             panic!("Gift already claimed");
         }
 
-        // Check if user already has an active subscription
-        if let Some(existing_sub) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user.clone()))
-        {
-            if existing_sub.status == SubscriptionStatus::Active {
-                panic!("User already has an active subscription");
-            }
+        if env.ledger().timestamp() < gift.deliverable_at {
+            panic!("Gift not yet deliverable");
         }
 
+        Self::replace_previous(&env, &user);
+
         let plan: SubscriptionPlan = env
             .storage()
             .persistent()
@@ -530,6 +1816,12 @@ impl SubscriptionContract {
             auto_renew: false,
             is_family_plan: false,
             family_members: Vec::new(&env),
+            payment_token: subscription::default_token(&env),
+            seats: 1,
+            seat_members: Vec::new(&env),
+            price_version: plan.price_version,
+            paid_amount: plan.price,
+            paid_from: current_time,
         };
 
         gift.claimed = true;
@@ -537,6 +1829,10 @@ impl SubscriptionContract {
         env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
         env.storage().persistent().set(&DataKey::GiftedSubscription(gift_id), &gift);
         env.storage().instance().set(&DataKey::NextSubscriptionId, &(subscription_id + 1));
+        analytics::report(&env, analytics::ACTIVE_SUBSCRIPTIONS, 1);
+        stats::record(&env, subscription.plan_id, |s| s.subscriptions += 1);
+        subscribers::add(&env, &user, subscription.plan_id);
+        membership::sync(&env, &subscription);
 
         events::emit_subscription_created(
             &env,
@@ -551,9 +1847,15 @@ impl SubscriptionContract {
         subscription_id
     }
 
+    /// Get gift details, including its message and delivery time
+    pub fn get_gift(env: Env, gift_id: u64) -> Option<GiftSubscription> {
+        env.storage().persistent().get(&DataKey::GiftedSubscription(gift_id))
+    }
+
     /// Toggle auto-renewal
     pub fn set_auto_renew(env: Env, user: Address, auto_renew: bool) {
         user.require_auth();
+        subscription::require_not_suspended(&env, &user);
 
         let mut subscription: UserSubscription = env
             .storage()
@@ -568,41 +1870,276 @@ impl SubscriptionContract {
 
     /// Check if subscription is active and handle grace period
     pub fn check_subscription_status(env: Env, user: Address) -> SubscriptionStatus {
-        let mut subscription: UserSubscription = env
+        let subscription: UserSubscription = env
             .storage()
             .persistent()
             .get(&DataKey::UserSubscription(user.clone()))
             .expect("Subscription not found");
 
+        Self::refresh_status(&env, subscription)
+    }
+
+    /// Move a batch of lapsed subscriptions into GracePeriod or Expired. Anyone may call
+    /// this; users without a subscription are skipped. Overdue pauses are ended along the
+    /// way, but only moves into GracePeriod or Expired count towards the returned number.
+    pub fn sweep_expired(env: Env, users: Vec<Address>) -> u32 {
+        let mut updated = 0;
+
+        for user in users.iter() {
+            if let Some(subscription) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user))
+            {
+                let status = subscription.status;
+                let new_status = Self::refresh_status(&env, subscription);
+                if new_status != status
+                    && matches!(new_status, SubscriptionStatus::GracePeriod | SubscriptionStatus::Expired)
+                {
+                    updated += 1;
+                }
+            }
+        }
+
+        updated
+    }
+
+    /// Emit `renewal_due` once per term when the subscription enters its renewal window
+    fn notify_renewal_due(env: &Env, subscription: &UserSubscription) {
+        let key = DataKey::RenewalNotified(subscription.subscription_id);
+        if env.storage().persistent().get::<DataKey, u64>(&key) == Some(subscription.end_date) {
+            return;
+        }
+
+        let plan = subscription::peek_renewal_plan(env, subscription);
+        let amount = subscription::renewal_amount(env, subscription, &plan);
+
+        env.storage().persistent().set(&key, &subscription.end_date);
+
+        events::emit_renewal_due(
+            env,
+            events::RenewalDueEvent {
+                subscription_id: subscription.subscription_id,
+                user: subscription.user.clone(),
+                due_at: subscription.end_date,
+                amount,
+            },
+        );
+    }
+
+    fn refresh_status(env: &Env, mut subscription: UserSubscription) -> SubscriptionStatus {
+        let user = subscription.user.clone();
         let current_time = env.ledger().timestamp();
 
-        if subscription.status == SubscriptionStatus::Active && current_time > subscription.end_date {
-            let grace_period_days: u32 = env.storage().instance().get(&DataKey::GracePeriod).unwrap();
-            let grace_period_end = subscription.end_date + (grace_period_days as u64 * 86400);
+        if subscription.status == SubscriptionStatus::Paused {
+            let paused_data: PausedSubscriptionData = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PausedSubscription(user.clone()))
+                .expect("Paused data not found");
+
+            if let Some(deadline) = subscription::pause_deadline(env, &subscription, &paused_data) {
+                if current_time > deadline {
+                    subscription = Self::end_pause(env, subscription);
+                }
+            }
+        }
+
+        if subscription.status == SubscriptionStatus::Active
+            && current_time <= subscription.end_date
+            && subscription.end_date - current_time <= 86400
+        {
+            Self::notify_renewal_due(env, &subscription);
+        }
 
-            if current_time <= grace_period_end {
-                subscription.status = SubscriptionStatus::GracePeriod;
-            } else {
+        let lapsed = match subscription.status {
+            SubscriptionStatus::Active => current_time > subscription.end_date,
+            SubscriptionStatus::GracePeriod | SubscriptionStatus::PastDue => true,
+            _ => false,
+        };
+
+        if lapsed {
+            let old_status = subscription.status;
+            let grace_period_end = subscription::grace_period_end(env, &subscription);
+
+            if current_time > grace_period_end {
                 subscription.status = SubscriptionStatus::Expired;
                 subscription.auto_renew = false;
+                env.storage().persistent().remove(&DataKey::Dunning(user.clone()));
+                analytics::report(env, analytics::ACTIVE_SUBSCRIPTIONS, -1);
+                stats::record(env, subscription.plan_id, |s| s.expirations += 1);
+                subscribers::remove(env, &user, subscription.plan_id);
+            } else if subscription.status == SubscriptionStatus::Active {
+                subscription.status = SubscriptionStatus::GracePeriod;
             }
 
-            env.storage().persistent().set(&DataKey::UserSubscription(user), &subscription);
+            env.storage().persistent().set(&DataKey::UserSubscription(user.clone()), &subscription);
+
+            if subscription.status != old_status {
+                events::emit_subscription_status_changed(
+                    env,
+                    events::SubscriptionStatusChangedEvent {
+                        subscription_id: subscription.subscription_id,
+                        user,
+                        old_status,
+                        new_status: subscription.status,
+                    },
+                );
+            }
         }
 
         subscription.status
     }
 
+    /// List subscribers with a live subscription, `limit` at most 100 per page
+    pub fn list_subscribers(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        subscribers::page(&env, &DataKey::Subscribers, offset, limit)
+    }
+
+    /// List a plan's live subscribers, `limit` at most 100 per page
+    pub fn list_subscribers_by_plan(env: Env, plan_id: u32, offset: u32, limit: u32) -> Vec<Address> {
+        subscribers::page(&env, &DataKey::PlanSubscribers(plan_id), offset, limit)
+    }
+
+    /// Get protocol-wide revenue, subscriber and churn counters
+    pub fn get_revenue_stats(env: Env) -> RevenueStats {
+        stats::global(&env)
+    }
+
+    /// Get revenue, subscriber and churn counters for a plan
+    pub fn get_plan_stats(env: Env, plan_id: u32) -> RevenueStats {
+        stats::plan(&env, plan_id)
+    }
+
     /// Get user subscription details
     pub fn get_subscription(env: Env, user: Address) -> Option<UserSubscription> {
         env.storage().persistent().get(&DataKey::UserSubscription(user))
     }
 
+    /// Get failed renewal attempts and the next retry time for a past-due subscription
+    pub fn get_dunning_state(env: Env, user: Address) -> Option<DunningState> {
+        subscription::dunning_state(&env, &user)
+    }
+
+    /// Get a subscriber's usage on a meter for the current billing period
+    pub fn get_usage(env: Env, user: Address, meter_id: Symbol) -> u64 {
+        match Self::get_subscription(env.clone(), user) {
+            Some(subscription) => metering::usage(&env, subscription.subscription_id, &meter_id),
+            None => 0,
+        }
+    }
+
+    /// Get a plan's limit for a meter
+    pub fn get_meter_limit(env: Env, plan_id: u32, meter_id: Symbol) -> Option<MeterLimit> {
+        env.storage().persistent().get(&DataKey::MeterLimit(plan_id, meter_id))
+    }
+
+    /// Get the plan a subscription is scheduled to switch to at renewal
+    pub fn get_scheduled_plan_change(env: Env, user: Address) -> Option<u32> {
+        let subscription = Self::get_subscription(env.clone(), user)?;
+        env.storage()
+            .persistent()
+            .get(&DataKey::ScheduledPlanChange(subscription.subscription_id))
+    }
+
+    /// Get the discount in bps for prepaying `periods` terms of a plan
+    pub fn get_prepay_discount(env: Env, plan_id: u32, periods: u32) -> u32 {
+        subscription::prepay_discount_bps(&env, plan_id, periods)
+    }
+
+    /// Token allowance the contract needs from the user to collect the next renewal.
+    /// Wallets should approve at least this much of the subscription's payment token.
+    pub fn get_required_allowance(env: Env, user: Address) -> i128 {
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user))
+        {
+            Some(subscription) if subscription.auto_renew => subscription::renewal_amount(
+                &env,
+                &subscription,
+                &subscription::peek_renewal_plan(&env, &subscription),
+            ),
+            _ => 0,
+        }
+    }
+
+    /// Get a user's renewal streak and the loyalty discount it earns
+    pub fn get_loyalty_status(env: Env, user: Address) -> LoyaltyStatus {
+        let streak = match env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user))
+        {
+            Some(subscription) => loyalty::streak(&env, &subscription),
+            None => 0,
+        };
+
+        LoyaltyStatus {
+            streak,
+            discount_bps: loyalty::discount_bps(&env, streak),
+            next_tier_at: loyalty::schedule(&env)
+                .iter()
+                .map(|tier| tier.min_renewals)
+                .find(|min_renewals| *min_renewals > streak),
+        }
+    }
+
+    /// Get the admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Admin).unwrap()
+    }
+
+    /// Get the proposed admin awaiting acceptance, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Get the admin hold on a user's subscription, if any
+    pub fn get_suspension(env: Env, user: Address) -> Option<Suspension> {
+        env.storage().persistent().get(&DataKey::Suspension(user))
+    }
+
+    /// Get the per-term price the subscription's current term was bought at, in its payment token
+    pub fn get_pinned_price(env: Env, user: Address) -> Option<i128> {
+        let subscription: UserSubscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserSubscription(user))?;
+
+        subscription::pinned_price(&env, &subscription)
+    }
+
+    /// Get every price a plan has had, oldest first
+    pub fn get_price_history(env: Env, plan_id: u32) -> Vec<PriceVersion> {
+        subscription::price_history(&env, plan_id)
+    }
+
+    /// Get a category bundle
+    pub fn get_bundle(env: Env, bundle_id: u32) -> Option<CategoryBundle> {
+        env.storage().persistent().get(&DataKey::Bundle(bundle_id))
+    }
+
     /// Get subscription plan details
     pub fn get_plan(env: Env, plan_id: u32) -> Option<SubscriptionPlan> {
         env.storage().persistent().get(&DataKey::SubscriptionPlan(plan_id))
     }
 
+    /// Get the address subscription payments are sent to
+    pub fn get_payment_recipient(env: Env) -> Address {
+        subscription::payment_recipient(&env)
+    }
+
+    /// Get revenue held by the contract in a token
+    pub fn get_treasury_balance(env: Env, token: Address) -> i128 {
+        subscription::treasury_balance(&env, &token)
+    }
+
+    /// Get the native XLM token contract, if configured
+    pub fn get_native_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::NativeToken)
+    }
+
     /// Check if user has access to a category
     pub fn has_category_access(env: Env, user: Address, category_id: u32) -> bool {
         if let Some(subscription) = env
@@ -610,27 +2147,84 @@ impl SubscriptionContract {
             .persistent()
             .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(user.clone()))
         {
-            if subscription.status != SubscriptionStatus::Active {
-                return false;
-            }
-
-            if let Some(plan) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, SubscriptionPlan>(&DataKey::SubscriptionPlan(subscription.plan_id))
+            if subscription::grants_access(&env, &subscription)
+                && subscription::plan_includes_category(&env, subscription.plan_id, category_id)
             {
-                return plan.category_ids.contains(&category_id);
+                return true;
             }
         }
 
-        // Check if user is a family member
-        false
+        // Check if user is a family member or holds a team seat
+        Self::check_family_access(env.clone(), user.clone(), category_id)
+            || Self::check_seat_access(env, user, category_id)
     }
 
     /// Check if family member has access
     pub fn check_family_access(env: Env, member: Address, category_id: u32) -> bool {
-        // This would need to iterate through all subscriptions to find if member is in any family plan
-        // For efficiency, consider maintaining a reverse index in production
-        false
+        // A member's own hold applies to subscriptions they share, not just ones they own
+        if subscription::is_suspended(&env, &member) {
+            return false;
+        }
+
+        let owner: Address = match env.storage().persistent().get(&DataKey::FamilyMemberOf(member.clone())) {
+            Some(owner) => owner,
+            None => return false,
+        };
+
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(owner))
+        {
+            Some(subscription) => {
+                subscription::grants_access(&env, &subscription)
+                    && subscription.family_members.contains(&member)
+                    && subscription::plan_includes_category(&env, subscription.plan_id, category_id)
+            }
+            None => false,
+        }
+    }
+
+    /// Get the owner whose subscription a family member shares
+    pub fn get_family_owner(env: Env, member: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::FamilyMemberOf(member))
+    }
+
+    /// Check if a team seat holder has access
+    pub fn check_seat_access(env: Env, member: Address, category_id: u32) -> bool {
+        if subscription::is_suspended(&env, &member) {
+            return false;
+        }
+
+        let owner: Address = match env.storage().persistent().get(&DataKey::SeatHolderOf(member.clone())) {
+            Some(owner) => owner,
+            None => return false,
+        };
+
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserSubscription>(&DataKey::UserSubscription(owner))
+        {
+            Some(subscription) => {
+                // Seats lapse if the owner has moved to a plan without them
+                let plan: SubscriptionPlan = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::SubscriptionPlan(subscription.plan_id))
+                    .expect("Plan not found");
+
+                plan.max_seats > 0
+                    && subscription::grants_access(&env, &subscription)
+                    && subscription.seat_members.contains(&member)
+                    && subscription::plan_includes_category(&env, subscription.plan_id, category_id)
+            }
+            None => false,
+        }
+    }
+
+    /// Get the team owner whose subscription assigned a seat to `member`
+    pub fn get_seat_owner(env: Env, member: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::SeatHolderOf(member))
     }
 }
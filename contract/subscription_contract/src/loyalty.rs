@@ -0,0 +1,48 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::storage_types::*;
+
+const BASIS_POINTS: u32 = 10_000;
+
+/// Discount tiers by consecutive renewals, lowest threshold first
+pub fn schedule(env: &Env) -> Vec<LoyaltyTier> {
+    env.storage()
+        .instance()
+        .get(&DataKey::LoyaltySchedule)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Consecutive renewals of a subscription. A lapse into `Expired` breaks the streak.
+pub fn streak(env: &Env, subscription: &UserSubscription) -> u32 {
+    match subscription.status {
+        SubscriptionStatus::Expired | SubscriptionStatus::Cancelled => 0,
+        _ => env
+            .storage()
+            .persistent()
+            .get(&DataKey::LoyaltyStreak(subscription.subscription_id))
+            .unwrap_or(0),
+    }
+}
+
+/// Discount earned by a streak of `streak` renewals
+pub fn discount_bps(env: &Env, streak: u32) -> u32 {
+    schedule(env)
+        .iter()
+        .filter(|tier| streak >= tier.min_renewals)
+        .map(|tier| tier.discount_bps)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Renewal price after the discount earned by the subscription's current streak
+pub fn apply_discount(env: &Env, subscription: &UserSubscription, price: i128) -> i128 {
+    price - price * discount_bps(env, streak(env, subscription)) as i128 / BASIS_POINTS as i128
+}
+
+/// Count a successful renewal. Call before the subscription's status is updated.
+pub fn record_renewal(env: &Env, subscription: &UserSubscription) {
+    env.storage().persistent().set(
+        &DataKey::LoyaltyStreak(subscription.subscription_id),
+        &(streak(env, subscription) + 1),
+    );
+}
@@ -0,0 +1,34 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::storage_types::*;
+
+/// Subset of the membership badge interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "MembershipBadgeClient")]
+pub trait MembershipBadgeInterface {
+    fn issue(env: Env, minter: Address, to: Address, plan_id: u32, expires_at: u64);
+    fn revoke(env: Env, minter: Address, holder: Address);
+}
+
+/// Mint or extend the subscriber's badge to match their current term, if a badge contract is configured
+pub fn sync(env: &Env, subscription: &UserSubscription) {
+    if let Some(badge) = badge_contract(env) {
+        MembershipBadgeClient::new(env, &badge).issue(
+            &env.current_contract_address(),
+            &subscription.user,
+            &subscription.plan_id,
+            &subscription.end_date,
+        );
+    }
+}
+
+/// Remove a holder's badge, if a badge contract is configured
+pub fn revoke(env: &Env, holder: &Address) {
+    if let Some(badge) = badge_contract(env) {
+        MembershipBadgeClient::new(env, &badge).revoke(&env.current_contract_address(), holder);
+    }
+}
+
+fn badge_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::MembershipBadge)
+}
@@ -0,0 +1,66 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::storage_types::*;
+use crate::subscription;
+
+/// Meters defined for a plan
+pub fn plan_meters(env: &Env, plan_id: u32) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlanMeters(plan_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Usage recorded against a meter in the current billing period
+pub fn usage(env: &Env, subscription_id: u64, meter_id: &Symbol) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Usage(subscription_id, meter_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn is_consumer(env: &Env, consumer: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MeteringConsumer(consumer.clone()))
+        .unwrap_or(false)
+}
+
+/// Overage owed for usage above each meter's included units, in the subscription's payment token.
+/// Overage prices are set in the default token and converted at the plan's own price ratio.
+pub fn overage_amount(env: &Env, subscription: &UserSubscription, plan: &SubscriptionPlan) -> i128 {
+    let mut overage = 0;
+
+    for meter_id in plan_meters(env, plan.plan_id).iter() {
+        let limit: MeterLimit = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MeterLimit(plan.plan_id, meter_id.clone()))
+            .unwrap();
+        let used = usage(env, subscription.subscription_id, &meter_id);
+
+        if used > limit.included_units {
+            overage += (used - limit.included_units) as i128 * limit.overage_price;
+        }
+    }
+
+    if overage == 0 || subscription.payment_token == subscription::default_token(env) {
+        return overage;
+    }
+
+    // Free plans have no price ratio to convert with
+    if plan.price == 0 {
+        return 0;
+    }
+
+    overage * subscription::plan_price(env, plan, &subscription.payment_token) / plan.price
+}
+
+/// Start a new billing period for every meter on the plan
+pub fn reset_usage(env: &Env, subscription: &UserSubscription, plan: &SubscriptionPlan) {
+    for meter_id in plan_meters(env, plan.plan_id).iter() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Usage(subscription.subscription_id, meter_id));
+    }
+}
@@ -0,0 +1,40 @@
+use soroban_sdk::{contractclient, symbol_short, Address, Env, Symbol};
+
+use crate::storage_types::*;
+
+/// Subset of the parameter registry interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn get_u32(env: Env, namespace: Symbol, key: Symbol) -> Option<u32>;
+}
+
+const NAMESPACE: Symbol = symbol_short!("subs");
+const GRACE_PERIOD_DAYS: Symbol = symbol_short!("grace");
+const MAX_RETRIES: Symbol = symbol_short!("retries");
+const RETRY_INTERVAL_DAYS: Symbol = symbol_short!("retry_day");
+
+/// Grace period from the governance registry, falling back to the value set at initialization
+pub fn grace_period_days(env: &Env) -> u32 {
+    read_u32(env, GRACE_PERIOD_DAYS)
+        .unwrap_or_else(|| env.storage().instance().get(&DataKey::GracePeriod).unwrap())
+}
+
+/// Dunning policy with any values the governance registry sets applied over `local`
+pub fn dunning_config(env: &Env, local: DunningConfig) -> DunningConfig {
+    DunningConfig {
+        max_retries: read_u32(env, MAX_RETRIES).unwrap_or(local.max_retries),
+        retry_interval_days: read_u32(env, RETRY_INTERVAL_DAYS)
+            .filter(|days| *days > 0)
+            .unwrap_or(local.retry_interval_days),
+    }
+}
+
+/// A registry value of the wrong type is ignored, so the local value still applies
+fn read_u32(env: &Env, key: Symbol) -> Option<u32> {
+    let registry: Address = env.storage().instance().get(&DataKey::Registry)?;
+    match RegistryClient::new(env, &registry).try_get_u32(&NAMESPACE, &key) {
+        Ok(Ok(value)) => value,
+        _ => None,
+    }
+}
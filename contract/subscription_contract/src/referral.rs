@@ -0,0 +1,90 @@
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contractclient, contracttype, vec, Address, Env, IntoVal, Symbol,
+};
+
+use crate::storage_types::*;
+use crate::subscription;
+
+const BASIS_POINTS: u32 = 10_000;
+
+/// Attribution record as stored by the referral contract
+#[derive(Clone)]
+#[contracttype]
+pub struct Attribution {
+    pub code: Symbol,
+    pub referrer: Address,
+    pub consumer: Address,
+    pub attributed_at: u64,
+}
+
+/// Subset of the shared referral contract interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "ReferralClient")]
+pub trait ReferralInterface {
+    fn attribute(env: Env, caller: Address, user: Address, code: Symbol);
+    fn accrue(env: Env, consumer: Address, user: Address, token: Address, amount: i128) -> i128;
+    fn get_attribution(env: Env, user: Address) -> Option<Attribution>;
+}
+
+/// Referral settings, if a referral contract is configured
+pub fn config(env: &Env) -> Option<ReferralConfig> {
+    env.storage().instance().get(&DataKey::ReferralConfig)
+}
+
+/// Attribute `user` to the owner of `code` in the referral contract
+pub fn attribute(env: &Env, user: &Address, code: &Symbol) {
+    let config = config(env).expect("Referrals not configured");
+    ReferralClient::new(env, &config.contract).attribute(&env.current_contract_address(), user, code);
+}
+
+/// Send the referral share of a payment to the referral contract, which splits it across the
+/// user's referral chain. Payments are rewarded until the user has paid for `reward_periods`
+/// terms since being referred; `paid_until` is the end of the time paid for before this payment.
+pub fn reward(
+    env: &Env,
+    user: &Address,
+    plan: &SubscriptionPlan,
+    paid_until: u64,
+    token: &Address,
+    amount: i128,
+) {
+    let config = match config(env) {
+        Some(config) => config,
+        None => return,
+    };
+
+    let client = ReferralClient::new(env, &config.contract);
+    let attribution = match client.get_attribution(user) {
+        Some(attribution) => attribution,
+        None => return,
+    };
+
+    let term = plan.duration_days as u64 * 86400;
+    if term == 0 || paid_until.saturating_sub(attribution.attributed_at) / term >= config.reward_periods as u64 {
+        return;
+    }
+
+    let share = amount * config.reward_bps as i128 / BASIS_POINTS as i128;
+    if share <= 0 {
+        return;
+    }
+
+    // The referral contract pulls the share from the treasury and sends back whatever
+    // its level shares leave unassigned
+    let contract = env.current_contract_address();
+    subscription::debit_treasury(env, token, share);
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (contract.clone(), config.contract.clone(), share).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+    let accrued = client.accrue(&contract, user, token, &share);
+    subscription::credit_treasury(env, token, share - accrued);
+}
@@ -0,0 +1,50 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::storage_types::*;
+
+/// Subset of the rewards token interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "RewardsTokenClient")]
+pub trait RewardsTokenInterface {
+    fn mint(env: Env, minter: Address, to: Address, amount: i128);
+    fn burn(env: Env, minter: Address, from: Address, amount: i128);
+}
+
+/// Mint renewal points to the user if a rewards token is configured
+pub fn award_renewal_points(env: &Env, user: &Address) {
+    if let Some(config) = env
+        .storage()
+        .instance()
+        .get::<DataKey, RewardsConfig>(&DataKey::RewardsConfig)
+    {
+        if config.points_per_renewal > 0 {
+            RewardsTokenClient::new(env, &config.token).mint(
+                &env.current_contract_address(),
+                user,
+                &config.points_per_renewal,
+            );
+        }
+    }
+}
+
+/// Burn the user's points and return the discount they buy against `price`
+pub fn redeem_points(env: &Env, user: &Address, points: i128, price: i128) -> i128 {
+    if points <= 0 {
+        return 0;
+    }
+
+    let config: RewardsConfig = env
+        .storage()
+        .instance()
+        .get(&DataKey::RewardsConfig)
+        .expect("Rewards not configured");
+
+    let discount = points * config.point_value;
+    if discount > price {
+        panic!("Discount exceeds price");
+    }
+
+    RewardsTokenClient::new(env, &config.token).burn(&env.current_contract_address(), user, &points);
+
+    discount
+}
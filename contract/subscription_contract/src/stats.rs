@@ -0,0 +1,36 @@
+use soroban_sdk::Env;
+
+use crate::storage_types::*;
+
+/// Protocol-wide counters
+pub fn global(env: &Env) -> RevenueStats {
+    read(env, &DataKey::RevenueStats)
+}
+
+/// Counters for one plan
+pub fn plan(env: &Env, plan_id: u32) -> RevenueStats {
+    read(env, &DataKey::PlanStats(plan_id))
+}
+
+/// Apply an update to both the protocol-wide and the plan's counters
+pub fn record(env: &Env, plan_id: u32, f: impl Fn(&mut RevenueStats)) {
+    update(env, DataKey::RevenueStats, &f);
+    update(env, DataKey::PlanStats(plan_id), &f);
+}
+
+pub fn update(env: &Env, key: DataKey, f: impl Fn(&mut RevenueStats)) {
+    let mut stats = read(env, &key);
+    f(&mut stats);
+    env.storage().persistent().set(&key, &stats);
+}
+
+fn read(env: &Env, key: &DataKey) -> RevenueStats {
+    env.storage().persistent().get(key).unwrap_or(RevenueStats {
+        revenue: 0,
+        refunds: 0,
+        subscriptions: 0,
+        active_subscribers: 0,
+        cancellations: 0,
+        expirations: 0,
+    })
+}
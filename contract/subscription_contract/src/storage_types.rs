@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String, Vec};
+use soroban_sdk::{contracttype, Address, Map, String, Symbol, Vec};
 
 #[derive(Clone)]
 #[contracttype]
@@ -13,6 +13,38 @@ pub enum DataKey {
     NextSubscriptionId,
     PausedSubscription(Address),
     GiftedSubscription(u64),
+    NextGiftId,
+    RewardsConfig,
+    Analytics,
+    Registry,
+    NativeToken,
+    Treasury(Address), // Token -> revenue held by the contract
+    DunningConfig,
+    Dunning(Address),
+    PendingTransfer(Address), // Current owner -> proposed new owner
+    MeteringConsumer(Address),
+    PlanMeters(u32),
+    MeterLimit(u32, Symbol), // (plan_id, meter_id)
+    Usage(u64, Symbol), // (subscription_id, meter_id) -> units this period
+    FamilyMemberOf(Address), // Member -> owner whose subscription they share
+    PauseCount(u64), // subscription_id -> pauses used this term
+    ScheduledPlanChange(u64), // subscription_id -> plan to switch to at renewal
+    MembershipBadge,
+    Subscribers, // Addresses with a live (not cancelled or expired) subscription
+    PlanSubscribers(u32),
+    RevenueStats,
+    PlanStats(u32),
+    RenewalNotified(u64), // subscription_id -> end date a renewal_due event was emitted for
+    SeatHolderOf(Address), // Seat holder -> team owner whose subscription assigned them
+    NextBundleId,
+    Bundle(u32),
+    PrepayDiscount(u32, u32), // (plan_id, periods) -> discount bps for paying that many terms upfront
+    ReferralConfig,
+    Suspension(Address), // User -> admin hold, kept across new subscriptions until lifted
+    PriceHistory(u32), // plan_id -> every price the plan has had, oldest first
+    LoyaltySchedule,
+    LoyaltyStreak(u64), // subscription_id -> consecutive renewals
+    PendingAdmin,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -22,7 +54,7 @@ pub enum SubscriptionTier {
     Annual,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 #[contracttype]
 pub enum SubscriptionStatus {
     Active,
@@ -30,6 +62,15 @@ pub enum SubscriptionStatus {
     Cancelled,
     Expired,
     GracePeriod,
+    PastDue, // Renewal charge failed; retries pending until the grace period ends
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[contracttype]
+pub enum RefundPolicy {
+    None,
+    FullWithinNDays(u32), // Full refund if cancelled within N days of the last payment
+    Prorated,
 }
 
 #[derive(Clone)]
@@ -42,6 +83,35 @@ pub struct SubscriptionPlan {
     pub category_ids: Vec<u32>,
     pub max_family_members: u32,
     pub is_active: bool,
+    pub token_prices: Map<Address, i128>, // Alternative payment tokens -> price in that token
+    pub deprecated: bool, // Closed to new subscribers; existing ones renew at the frozen price
+    pub sunset_date: Option<u64>, // After this, renewals migrate to the successor plan
+    pub successor_plan_id: Option<u32>,
+    pub max_pause_days: u32, // 0 = unlimited; longer pauses end automatically
+    pub max_pauses_per_term: u32, // 0 = unlimited
+    pub refund_policy: RefundPolicy,
+    pub max_seats: u32, // 0 = not a team plan; otherwise the price is per seat
+    pub bundle_ids: Vec<u32>, // Category bundles granted in addition to category_ids
+    pub price_version: u32, // Current entry in the plan's price history, starting at 1
+}
+
+/// A plan price and when it took effect
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PriceVersion {
+    pub version: u32,
+    pub price: i128, // In the default token
+    pub token_prices: Map<Address, i128>, // Prices in other accepted tokens
+    pub effective_at: u64,
+}
+
+/// Named set of categories that plans can reference instead of listing IDs
+#[derive(Clone)]
+#[contracttype]
+pub struct CategoryBundle {
+    pub bundle_id: u32,
+    pub name: String,
+    pub category_ids: Vec<u32>,
 }
 
 #[derive(Clone)]
@@ -57,6 +127,12 @@ pub struct UserSubscription {
     pub auto_renew: bool,
     pub is_family_plan: bool,
     pub family_members: Vec<Address>,
+    pub payment_token: Address,
+    pub seats: u32, // Seats paid for; always 1 outside team plans
+    pub seat_members: Vec<Address>,
+    pub price_version: u32, // Plan price version the current term was paid under
+    pub paid_amount: i128, // Paid for the time from paid_from to end_date, net of discounts and points
+    pub paid_from: u64,
 }
 
 #[derive(Clone)]
@@ -66,6 +142,39 @@ pub struct PausedSubscriptionData {
     pub remaining_days: u32,
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct DunningConfig {
+    pub max_retries: u32,
+    pub retry_interval_days: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct DunningState {
+    pub failed_attempts: u32,
+    pub next_retry_at: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct MeterLimit {
+    pub included_units: u64,
+    pub overage_price: i128, // Per unit above the included amount, in the default token
+}
+
+/// Dashboard counters. Revenue and refunds are in the default token only.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RevenueStats {
+    pub revenue: i128,
+    pub refunds: i128,
+    pub subscriptions: u64, // New subscriptions ever started, including claimed gifts
+    pub active_subscribers: u32,
+    pub cancellations: u32,
+    pub expirations: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct GiftSubscription {
@@ -75,4 +184,46 @@ pub struct GiftSubscription {
     pub plan_id: u32,
     pub claimed: bool,
     pub created_at: u64,
+    pub message: String,
+    pub deliverable_at: u64, // Claims are rejected before this time
+    pub announced: bool, // gift_deliverable has been emitted
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct RewardsConfig {
+    pub token: Address,
+    pub points_per_renewal: i128,
+    pub point_value: i128, // Payment token units one point is worth when redeemed
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ReferralConfig {
+    pub contract: Address, // Shared referral contract holding codes, attributions and earnings
+    pub reward_bps: u32, // Share of each rewarded payment passed to the referral contract
+    pub reward_periods: u32, // Terms paid for after signup that earn rewards, counting the signup
+}
+
+/// Admin hold placed on a subscription, e.g. while a chargeback is investigated
+#[derive(Clone)]
+#[contracttype]
+pub struct Suspension {
+    pub reason: String,
+    pub suspended_at: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct LoyaltyTier {
+    pub min_renewals: u32, // Consecutive renewals needed before the discount applies
+    pub discount_bps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct LoyaltyStatus {
+    pub streak: u32,
+    pub discount_bps: u32, // Applied to the next renewal
+    pub next_tier_at: Option<u32>, // Streak that unlocks the next higher discount
 }
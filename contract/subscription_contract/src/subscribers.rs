@@ -0,0 +1,60 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::stats;
+use crate::storage_types::*;
+
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Record a live subscriber in the global and per-plan indexes
+pub fn add(env: &Env, user: &Address, plan_id: u32) {
+    for (key, stats_key) in [
+        (DataKey::Subscribers, DataKey::RevenueStats),
+        (DataKey::PlanSubscribers(plan_id), DataKey::PlanStats(plan_id)),
+    ] {
+        let mut index = read(env, &key);
+        if !index.contains(user) {
+            index.push_back(user.clone());
+            env.storage().persistent().set(&key, &index);
+            stats::update(env, stats_key, |s| s.active_subscribers += 1);
+        }
+    }
+}
+
+/// Drop a subscriber from the indexes. Returns false if they were not listed under the plan.
+pub fn remove(env: &Env, user: &Address, plan_id: u32) -> bool {
+    let mut listed = false;
+
+    for (key, stats_key) in [
+        (DataKey::Subscribers, DataKey::RevenueStats),
+        (DataKey::PlanSubscribers(plan_id), DataKey::PlanStats(plan_id)),
+    ] {
+        let mut index = read(env, &key);
+        if let Some(position) = index.first_index_of(user) {
+            index.remove(position);
+            env.storage().persistent().set(&key, &index);
+            stats::update(env, stats_key, |s| s.active_subscribers -= 1);
+            listed = true;
+        }
+    }
+
+    listed
+}
+
+/// Move a listed subscriber from one plan's index to another's
+pub fn move_plan(env: &Env, user: &Address, old_plan_id: u32, new_plan_id: u32) {
+    if remove(env, user, old_plan_id) {
+        add(env, user, new_plan_id);
+    }
+}
+
+/// Read a page of an index
+pub fn page(env: &Env, key: &DataKey, offset: u32, limit: u32) -> Vec<Address> {
+    let index = read(env, key);
+    let start = offset.min(index.len());
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(index.len());
+    index.slice(start..end)
+}
+
+fn read(env: &Env, key: &DataKey) -> Vec<Address> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
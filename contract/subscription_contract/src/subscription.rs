@@ -1,100 +1,521 @@
-use soroban_sdk::{token, Address, Env};
+use soroban_sdk::{token, Address, Env, Vec};
 
+use crate::analytics;
+use crate::events;
+use crate::loyalty;
+use crate::metering;
+use crate::params;
+use crate::stats;
 use crate::storage_types::*;
+use crate::subscribers;
 
 /// Process subscription payment using Soroban token
 pub fn process_subscription_payment(env: &Env, user: &Address, plan: &SubscriptionPlan) {
-    let token_address: Address = env.storage().instance().get(&DataKey::TokenAddress).unwrap();
-    let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    process_payment(env, user, plan.plan_id, &default_token(env), plan.price);
+}
+
+/// Transfer a payment amount in `token` from the user for `plan_id`
+pub fn process_payment(env: &Env, user: &Address, plan_id: u32, token: &Address, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
 
-    let token_client = token::TokenClient::new(env, &token_address);
+    let token_client = token::TokenClient::new(env, token);
     
-    // Transfer tokens from user to contract admin
-    token_client.transfer(user, &admin, &plan.price);
+    // Transfer tokens from user into the contract treasury
+    token_client.transfer(user, payment_recipient(env), &amount);
+    credit_treasury(env, token, amount);
+
+    if *token == default_token(env) {
+        analytics::report(env, analytics::VOLUME, amount);
+        stats::record(env, plan_id, |s| s.revenue += amount);
+    }
 }
 
-/// Process refund to user
-pub fn process_refund(env: &Env, user: &Address, amount: i128) {
+/// Pull a renewal payment from the user's pre-approved allowance.
+/// Returns false instead of panicking when the allowance or balance is insufficient.
+pub fn collect_renewal_payment(
+    env: &Env,
+    user: &Address,
+    plan_id: u32,
+    token: &Address,
+    amount: i128,
+) -> bool {
+    if amount <= 0 {
+        return true;
+    }
+
+    let token_client = token::TokenClient::new(env, token);
+    let contract = env.current_contract_address();
+
+    if token_client
+        .try_transfer_from(&contract, user, &contract, &amount)
+        .is_err()
+    {
+        return false;
+    }
+    credit_treasury(env, token, amount);
+
+    if *token == default_token(env) {
+        analytics::report(env, analytics::VOLUME, amount);
+        stats::record(env, plan_id, |s| s.revenue += amount);
+    }
+
+    true
+}
+
+/// Token configured at initialization; plans are always payable in it at `plan.price`
+pub fn default_token(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::TokenAddress).unwrap()
+}
+
+/// Stellar Asset Contract wrapping native XLM, if configured
+pub fn native_token(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::NativeToken)
+        .expect("Native token not configured")
+}
+
+/// Price of a plan when paid in `token`
+pub fn plan_price(env: &Env, plan: &SubscriptionPlan, token: &Address) -> i128 {
+    if *token == default_token(env) {
+        return plan.price;
+    }
+
+    plan.token_prices.get(token.clone()).expect("Token not accepted for plan")
+}
+
+/// Price of one term of a subscription in `token`; team plans are charged per seat.
+/// Subscribers renewing on a deprecated plan keep the price version they are pinned to.
+pub fn subscription_price(env: &Env, subscription: &UserSubscription, plan: &SubscriptionPlan, token: &Address) -> i128 {
+    grandfathered_price(env, subscription, plan, token)
+        .unwrap_or_else(|| plan_price(env, plan, token) * subscription.seats as i128)
+}
+
+/// Pinned price of a subscription staying on its deprecated plan, if that version accepted
+/// `token`. `subscription` must not have been migrated to `plan` yet.
+fn grandfathered_price(env: &Env, subscription: &UserSubscription, plan: &SubscriptionPlan, token: &Address) -> Option<i128> {
+    if !plan.deprecated || plan.plan_id != subscription.plan_id {
+        return None;
+    }
+
+    pinned_price_in(env, subscription, token)
+}
+
+/// Price version a renewal into `plan` pins the subscription to
+pub fn renewal_price_version(env: &Env, subscription: &UserSubscription, plan: &SubscriptionPlan, token: &Address) -> u32 {
+    match grandfathered_price(env, subscription, plan, token) {
+        Some(_) => subscription.price_version,
+        None => plan.price_version,
+    }
+}
+
+/// Prices a plan has had, oldest first
+pub fn price_history(env: &Env, plan_id: u32) -> Vec<PriceVersion> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PriceHistory(plan_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Append the plan's current prices, in every accepted token, to its history
+pub fn record_price_version(env: &Env, plan: &SubscriptionPlan) {
+    let mut history = price_history(env, plan.plan_id);
+    history.push_back(PriceVersion {
+        version: plan.price_version,
+        price: plan.price,
+        token_prices: plan.token_prices.clone(),
+        effective_at: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&DataKey::PriceHistory(plan.plan_id), &history);
+}
+
+/// Price of one term of the subscription in its payment token under the price version it
+/// is pinned to, or None if that version did not accept the token
+pub fn pinned_price(env: &Env, subscription: &UserSubscription) -> Option<i128> {
+    pinned_price_in(env, subscription, &subscription.payment_token)
+}
+
+fn pinned_price_in(env: &Env, subscription: &UserSubscription, token: &Address) -> Option<i128> {
+    let version = price_history(env, subscription.plan_id).get(subscription.price_version.checked_sub(1)?)?;
+    let price = if *token == default_token(env) {
+        version.price
+    } else {
+        version.token_prices.get(token.clone())?
+    };
+
+    Some(price * subscription.seats as i128)
+}
+
+/// Discount in bps for paying `periods` terms of a plan upfront
+pub fn prepay_discount_bps(env: &Env, plan_id: u32, periods: u32) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PrepayDiscount(plan_id, periods))
+        .unwrap_or(0)
+}
+
+/// Total for `periods` terms at `price` each, less any prepay discount
+pub fn prepaid_price(env: &Env, plan_id: u32, price: i128, periods: u32) -> i128 {
+    let total = price * periods as i128;
+    total - total * prepay_discount_bps(env, plan_id, periods) as i128 / 10000
+}
+
+/// Address that receives subscription payments; revenue is held here until withdrawn
+pub fn payment_recipient(env: &Env) -> Address {
+    env.current_contract_address()
+}
+
+/// Revenue held by the contract in `token`
+pub fn treasury_balance(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Treasury(token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn credit_treasury(env: &Env, token: &Address, amount: i128) {
+    let balance = treasury_balance(env, token);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Treasury(token.clone()), &(balance + amount));
+}
+
+pub fn debit_treasury(env: &Env, token: &Address, amount: i128) {
+    let balance = treasury_balance(env, token);
+    if amount > balance {
+        panic!("Insufficient treasury balance");
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Treasury(token.clone()), &(balance - amount));
+}
+
+/// Process refund to user in the token they paid with
+pub fn process_refund(env: &Env, user: &Address, plan_id: u32, token: &Address, amount: i128) {
     if amount <= 0 {
         return;
     }
 
-    let token_address: Address = env.storage().instance().get(&DataKey::TokenAddress).unwrap();
-    let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    debit_treasury(env, token, amount);
 
-    let token_client = token::TokenClient::new(env, &token_address);
+    let token_client = token::TokenClient::new(env, token);
     
-    // Transfer tokens from admin back to user
-    token_client.transfer(&admin, user, &amount);
+    // Pay the refund out of held revenue
+    token_client.transfer(&env.current_contract_address(), user, &amount);
+
+    if *token == default_token(env) {
+        analytics::report(env, analytics::REFUNDS, amount);
+        stats::record(env, plan_id, |s| s.refunds += amount);
+    }
 }
 
-/// Calculate prorated refund amount based on remaining subscription time
-pub fn calculate_prorated_refund(
-    env: &Env,
-    subscription: &UserSubscription,
-    plan: &SubscriptionPlan,
-) -> i128 {
+/// Plan a subscription renews into. A scheduled plan change takes effect here, and
+/// subscribers of a sunset plan are moved to its successor.
+pub fn renewal_plan(env: &Env, subscription: &mut UserSubscription) -> SubscriptionPlan {
     let current_time = env.ledger().timestamp();
-    
-    if current_time >= subscription.end_date {
-        return 0;
+
+    let scheduled_key = DataKey::ScheduledPlanChange(subscription.subscription_id);
+    if let Some(new_plan_id) = env.storage().persistent().get::<DataKey, u32>(&scheduled_key) {
+        env.storage().persistent().remove(&scheduled_key);
+
+        let old_plan_id = subscription.plan_id;
+        subscription.plan_id = new_plan_id;
+        subscribers::move_plan(env, &subscription.user, old_plan_id, new_plan_id);
+
+        events::emit_subscription_upgraded(
+            env,
+            events::SubscriptionUpgradedEvent {
+                subscription_id: subscription.subscription_id,
+                user: subscription.user.clone(),
+                old_plan_id,
+                new_plan_id,
+                prorated_amount: 0,
+            },
+        );
     }
 
-    let total_duration = subscription.end_date - subscription.start_date;
-    let remaining_duration = subscription.end_date - current_time;
+    let mut plan: SubscriptionPlan = env
+        .storage()
+        .persistent()
+        .get(&DataKey::SubscriptionPlan(subscription.plan_id))
+        .expect("Plan not found");
+
+    while let (Some(sunset_date), Some(successor_plan_id)) = (plan.sunset_date, plan.successor_plan_id) {
+        if current_time < sunset_date {
+            break;
+        }
 
-    if total_duration == 0 {
+        let old_plan_id = plan.plan_id;
+        plan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(successor_plan_id))
+            .expect("Plan not found");
+        subscription.plan_id = successor_plan_id;
+        subscribers::move_plan(env, &subscription.user, old_plan_id, successor_plan_id);
+
+        events::emit_plan_migrated(
+            env,
+            events::PlanMigratedEvent {
+                subscription_id: subscription.subscription_id,
+                user: subscription.user.clone(),
+                old_plan_id,
+                new_plan_id: successor_plan_id,
+            },
+        );
+    }
+
+    plan
+}
+
+/// Plan the subscription would renew into right now, without applying the change
+pub fn peek_renewal_plan(env: &Env, subscription: &UserSubscription) -> SubscriptionPlan {
+    let current_time = env.ledger().timestamp();
+
+    let plan_id = env
+        .storage()
+        .persistent()
+        .get::<DataKey, u32>(&DataKey::ScheduledPlanChange(subscription.subscription_id))
+        .unwrap_or(subscription.plan_id);
+
+    let mut plan: SubscriptionPlan = env
+        .storage()
+        .persistent()
+        .get(&DataKey::SubscriptionPlan(plan_id))
+        .expect("Plan not found");
+
+    while let (Some(sunset_date), Some(successor_plan_id)) = (plan.sunset_date, plan.successor_plan_id) {
+        if current_time < sunset_date {
+            break;
+        }
+
+        plan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriptionPlan(successor_plan_id))
+            .expect("Plan not found");
+    }
+
+    plan
+}
+
+/// Amount a renewal into `plan` charges, in the payment token: the seat price less
+/// any loyalty discount, plus metered overage. Overage is billed under the plan the usage
+/// was recorded on, so `subscription` must not have been migrated to `plan` yet.
+pub fn renewal_amount(env: &Env, subscription: &UserSubscription, plan: &SubscriptionPlan) -> i128 {
+    let current_plan: SubscriptionPlan = env
+        .storage()
+        .persistent()
+        .get(&DataKey::SubscriptionPlan(subscription.plan_id))
+        .expect("Plan not found");
+    let price = subscription_price(env, subscription, plan, &subscription.payment_token);
+
+    loyalty::apply_discount(env, subscription, price)
+        + metering::overage_amount(env, subscription, &current_plan)
+}
+
+/// Time at which a pause ends on its own under the plan's pause policy
+pub fn pause_deadline(env: &Env, subscription: &UserSubscription, paused: &PausedSubscriptionData) -> Option<u64> {
+    let plan: SubscriptionPlan = env
+        .storage()
+        .persistent()
+        .get(&DataKey::SubscriptionPlan(subscription.plan_id))
+        .expect("Plan not found");
+
+    if plan.max_pause_days == 0 {
+        return None;
+    }
+
+    Some(paused.paused_at + (plan.max_pause_days as u64 * 86400))
+}
+
+/// Check whether a subscription currently entitles its holder to access. Lapsed
+/// subscriptions keep access through the grace period, including while past due.
+pub fn grants_access(env: &Env, subscription: &UserSubscription) -> bool {
+    if is_suspended(env, &subscription.user) {
+        return false;
+    }
+
+    match subscription.status {
+        SubscriptionStatus::Active | SubscriptionStatus::GracePeriod | SubscriptionStatus::PastDue => {
+            env.ledger().timestamp() <= grace_period_end(env, subscription)
+        }
+        _ => false,
+    }
+}
+
+/// Check whether an admin has put the user's subscriptions on hold
+pub fn is_suspended(env: &Env, user: &Address) -> bool {
+    env.storage().persistent().has(&DataKey::Suspension(user.clone()))
+}
+
+/// Reject user actions while an admin hold is in place
+pub fn require_not_suspended(env: &Env, user: &Address) {
+    if is_suspended(env, user) {
+        panic!("Subscription is suspended");
+    }
+}
+
+/// Only subscriptions in good standing can change hands; a lapsed or past-due one cannot
+pub fn require_transferable(subscription: &UserSubscription) {
+    match subscription.status {
+        SubscriptionStatus::Active | SubscriptionStatus::Paused => {}
+        _ => panic!("Subscription cannot be transferred"),
+    }
+}
+
+/// Check whether a plan grants access to a category
+pub fn plan_includes_category(env: &Env, plan_id: u32, category_id: u32) -> bool {
+    match env
+        .storage()
+        .persistent()
+        .get::<DataKey, SubscriptionPlan>(&DataKey::SubscriptionPlan(plan_id))
+    {
+        Some(plan) => {
+            plan.category_ids.contains(category_id)
+                || plan.bundle_ids.iter().any(|bundle_id| {
+                    env.storage()
+                        .persistent()
+                        .get::<DataKey, CategoryBundle>(&DataKey::Bundle(bundle_id))
+                        .is_some_and(|bundle| bundle.category_ids.contains(category_id))
+                })
+        }
+        None => false,
+    }
+}
+
+/// Refund owed on cancellation under the plan's refund policy. A full refund returns
+/// what was actually charged for the term, after any discounts and points.
+pub fn calculate_refund(env: &Env, subscription: &UserSubscription, plan: &SubscriptionPlan) -> i128 {
+    match plan.refund_policy {
+        RefundPolicy::None => 0,
+        RefundPolicy::FullWithinNDays(days) => {
+            let window_end = subscription.last_payment_date + (days as u64 * 86400);
+            if env.ledger().timestamp() <= window_end {
+                subscription.paid_amount
+            } else {
+                0
+            }
+        }
+        RefundPolicy::Prorated => calculate_prorated_refund(env, subscription),
+    }
+}
+
+/// Unused part of what was paid for the current term as of `at`. A payment is spread evenly
+/// over the time it covers, so prepay and loyalty discounts carry through to refunds.
+pub fn credit_at(subscription: &UserSubscription, at: u64) -> i128 {
+    if at >= subscription.end_date || subscription.end_date <= subscription.paid_from {
         return 0;
     }
 
-    // Calculate prorated refund: (remaining_time / total_time) * price
-    let refund = (plan.price * remaining_duration as i128) / total_duration as i128;
-    
-    refund
+    let covered = subscription.end_date - subscription.paid_from;
+    let remaining = subscription.end_date - at.max(subscription.paid_from);
+
+    (subscription.paid_amount * remaining as i128) / covered as i128
+}
+
+/// Unused credit on the subscription right now. A paused term stops using credit while paused.
+pub fn remaining_credit(env: &Env, subscription: &UserSubscription) -> i128 {
+    let paused: Option<PausedSubscriptionData> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PausedSubscription(subscription.user.clone()));
+
+    let at = match paused {
+        Some(paused) if subscription.status == SubscriptionStatus::Paused => paused.paused_at,
+        _ => env.ledger().timestamp(),
+    };
+
+    credit_at(subscription, at)
+}
+
+/// Calculate prorated refund amount based on remaining subscription time
+pub fn calculate_prorated_refund(env: &Env, subscription: &UserSubscription) -> i128 {
+    remaining_credit(env, subscription)
 }
 
 /// Calculate amount for plan change (upgrade/downgrade)
 pub fn calculate_plan_change_amount(
     env: &Env,
     subscription: &UserSubscription,
-    old_plan: &SubscriptionPlan,
     new_plan: &SubscriptionPlan,
 ) -> i128 {
     let current_time = env.ledger().timestamp();
+    let new_price = subscription_price(env, subscription, new_plan, &subscription.payment_token);
     
     if current_time >= subscription.end_date {
         // Subscription expired, charge full new plan price
-        return new_plan.price;
+        return new_price;
     }
 
-    let total_duration = subscription.end_date - subscription.start_date;
     let remaining_duration = subscription.end_date - current_time;
 
-    if total_duration == 0 {
-        return new_plan.price;
-    }
-
-    // Calculate remaining value of old plan
-    let old_plan_remaining_value = (old_plan.price * remaining_duration as i128) / total_duration as i128;
-    
     // Calculate prorated value of new plan for remaining time
-    let new_plan_prorated_value = (new_plan.price * remaining_duration as i128) / (new_plan.duration_days as i128 * 86400);
+    let new_plan_prorated_value = (new_price * remaining_duration as i128) / (new_plan.duration_days as i128 * 86400);
+
+    // Positive means upgrade (user pays), negative means downgrade (user gets refund).
+    // The old plan is valued at what was actually paid for the time left.
+    new_plan_prorated_value - remaining_credit(env, subscription)
+}
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_RETRY_INTERVAL_DAYS: u32 = 1;
+
+/// Retry policy for failed renewal charges
+pub fn dunning_config(env: &Env) -> DunningConfig {
+    let local = env
+        .storage()
+        .instance()
+        .get(&DataKey::DunningConfig)
+        .unwrap_or(DunningConfig {
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_interval_days: DEFAULT_RETRY_INTERVAL_DAYS,
+        });
+
+    params::dunning_config(env, local)
+}
 
-    // Positive means upgrade (user pays), negative means downgrade (user gets refund)
-    new_plan_prorated_value - old_plan_remaining_value
+/// Failed renewal attempts recorded against a past-due subscription
+pub fn dunning_state(env: &Env, user: &Address) -> Option<DunningState> {
+    env.storage().persistent().get(&DataKey::Dunning(user.clone()))
+}
+
+/// End of the grace period following the current term
+pub fn grace_period_end(env: &Env, subscription: &UserSubscription) -> u64 {
+    subscription.end_date + (params::grace_period_days(env) as u64 * 86400)
 }
 
 /// Check if payment is due for renewal
 pub fn is_payment_due(env: &Env, subscription: &UserSubscription) -> bool {
     let current_time = env.ledger().timestamp();
     
-    // Check if subscription is about to expire (within 1 day)
-    if subscription.auto_renew && subscription.status == SubscriptionStatus::Active {
-        let time_until_expiry = subscription.end_date.saturating_sub(current_time);
-        return time_until_expiry <= 86400; // 1 day in seconds
+    if !subscription.auto_renew || is_suspended(env, &subscription.user) {
+        return false;
     }
-    
-    false
+
+    match subscription.status {
+        SubscriptionStatus::Active | SubscriptionStatus::GracePeriod | SubscriptionStatus::PastDue => {}
+        _ => return false,
+    }
+
+    // Lapsed past the grace period: the subscription is expired, not due
+    if current_time > grace_period_end(env, subscription) {
+        return false;
+    }
+
+    // Past-due subscriptions wait for their next retry slot
+    if subscription.status == SubscriptionStatus::PastDue {
+        return match dunning_state(env, &subscription.user) {
+            Some(state) => current_time >= state.next_retry_at,
+            None => true,
+        };
+    }
+
+    // Check if subscription is about to expire (within 1 day)
+    let time_until_expiry = subscription.end_date.saturating_sub(current_time);
+    time_until_expiry <= 86400 // 1 day in seconds
 }
 
 /// Validate subscription can be modified
@@ -105,6 +526,7 @@ pub fn validate_subscription_modification(subscription: &UserSubscription) -> Re
         SubscriptionStatus::Cancelled => Err("Subscription is cancelled"),
         SubscriptionStatus::Expired => Err("Subscription is expired"),
         SubscriptionStatus::Paused => Err("Subscription is paused"),
+        SubscriptionStatus::PastDue => Err("Subscription is past due"),
     }
 }
 
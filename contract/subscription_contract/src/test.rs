@@ -2,8 +2,9 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
-    token, Address, Env, String, Vec,
+    symbol_short,
+    testutils::{Address as _, Ledger, LedgerInfo, MockAuth, MockAuthInvoke},
+    token, Address, Env, IntoVal, Map, String, Vec,
 };
 
 fn create_token_contract<'a>(e: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
@@ -91,7 +92,7 @@ fn test_subscribe() {
     let subscription = contract.get_subscription(&user).unwrap();
     assert_eq!(subscription.plan_id, plan_id);
     assert_eq!(subscription.status, SubscriptionStatus::Active);
-    assert_eq!(subscription.auto_renew, true);
+    assert!(subscription.auto_renew);
 }
 
 #[test]
@@ -127,7 +128,7 @@ fn test_cancel_subscription_with_refund() {
     // Advance time by 15 days (half the subscription period)
     env.ledger().set(LedgerInfo {
         timestamp: env.ledger().timestamp() + 15 * 86400,
-        protocol_version: 20,
+        protocol_version: env.ledger().get().protocol_version,
         sequence_number: env.ledger().sequence(),
         network_id: Default::default(),
         base_reserve: 10,
@@ -261,7 +262,7 @@ fn test_family_plan() {
     contract.add_family_member(&owner, &member2);
 
     let subscription = contract.get_subscription(&owner).unwrap();
-    assert_eq!(subscription.is_family_plan, true);
+    assert!(subscription.is_family_plan);
     assert_eq!(subscription.family_members.len(), 2);
 
     // Remove a family member
@@ -297,7 +298,13 @@ fn test_gift_subscription() {
     );
 
     // Gift subscription
-    let gift_id = contract.gift_subscription(&gifter, &recipient, &plan_id);
+    let gift_id = contract.gift_subscription(
+        &gifter,
+        &recipient,
+        &plan_id,
+        &String::from_str(&env, "Enjoy!"),
+        &0,
+    );
 
     // Recipient claims gift
     let subscription_id = contract.claim_gift(&recipient, &gift_id);
@@ -305,7 +312,7 @@ fn test_gift_subscription() {
     let subscription = contract.get_subscription(&recipient).unwrap();
     assert_eq!(subscription.subscription_id, subscription_id);
     assert_eq!(subscription.plan_id, plan_id);
-    assert_eq!(subscription.auto_renew, false); // Gifted subscriptions don't auto-renew
+    assert!(!subscription.auto_renew); // Gifted subscriptions don't auto-renew
 }
 
 #[test]
@@ -340,7 +347,7 @@ fn test_auto_renew() {
     // Advance time past subscription end
     env.ledger().set(LedgerInfo {
         timestamp: original_end_date + 1,
-        protocol_version: 20,
+        protocol_version: env.ledger().get().protocol_version,
         sequence_number: env.ledger().sequence(),
         network_id: Default::default(),
         base_reserve: 10,
@@ -389,7 +396,7 @@ fn test_grace_period() {
     // Advance time past subscription end but within grace period
     env.ledger().set(LedgerInfo {
         timestamp: end_date + 3 * 86400, // 3 days after expiry
-        protocol_version: 20,
+        protocol_version: env.ledger().get().protocol_version,
         sequence_number: env.ledger().sequence(),
         network_id: Default::default(),
         base_reserve: 10,
@@ -494,3 +501,2663 @@ fn test_update_plan() {
     assert_eq!(plan.price, 1500);
     assert_eq!(plan.category_ids.len(), 5);
 }
+
+mod mock_rewards {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockRewardsToken;
+
+    #[contractimpl]
+    impl MockRewardsToken {
+        pub fn mint(env: Env, minter: Address, to: Address, amount: i128) {
+            minter.require_auth();
+            let balance: i128 = env.storage().persistent().get(&to).unwrap_or(0);
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn burn(env: Env, minter: Address, from: Address, amount: i128) {
+            minter.require_auth();
+            from.require_auth();
+            let balance: i128 = env.storage().persistent().get(&from).unwrap_or(0);
+            if amount > balance {
+                panic!("Insufficient balance");
+            }
+            env.storage().persistent().set(&from, &(balance - amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_renewal_earns_and_redeems_points() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+    let rewards = mock_rewards::MockRewardsTokenClient::new(
+        &env,
+        &env.register(mock_rewards::MockRewardsToken, ()),
+    );
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_rewards_config(&rewards.address, &50, &2);
+
+    let category_ids = Vec::from_array(&env, [1, 2, 3]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+    contract.renew_subscription(&user);
+    assert_eq!(rewards.balance(&user), 50);
+    assert_eq!(token_client.balance(&user), 8000);
+
+    // 40 points at 2 units each take 80 off the next renewal
+    contract.renew_with_points(&user, &40);
+    assert_eq!(rewards.balance(&user), 60);
+    assert_eq!(token_client.balance(&user), 7080);
+}
+
+mod mock_analytics {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    #[contract]
+    pub struct MockAnalytics;
+
+    #[contractimpl]
+    impl MockAnalytics {
+        pub fn report(env: Env, reporter: Address, metric: Symbol, delta: i128) {
+            reporter.require_auth();
+            let total: i128 = env.storage().persistent().get(&metric).unwrap_or(0);
+            env.storage().persistent().set(&metric, &(total + delta));
+        }
+
+        pub fn get_total(env: Env, metric: Symbol) -> i128 {
+            env.storage().persistent().get(&metric).unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_reports_analytics() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+    let analytics = mock_analytics::MockAnalyticsClient::new(
+        &env,
+        &env.register(mock_analytics::MockAnalytics, ()),
+    );
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_analytics(&analytics.address);
+
+    let category_ids = Vec::from_array(&env, [1, 2, 3]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+    assert_eq!(analytics.get_total(&analytics::VOLUME), 1000);
+    assert_eq!(analytics.get_total(&analytics::ACTIVE_SUBSCRIPTIONS), 1);
+
+    contract.cancel_subscription(&user);
+    assert_eq!(analytics.get_total(&analytics::REFUNDS), 1000);
+    assert_eq!(analytics.get_total(&analytics::ACTIVE_SUBSCRIPTIONS), 0);
+}
+
+#[test]
+fn test_resubscribing_over_lapsed_term_keeps_active_count() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+    let analytics = mock_analytics::MockAnalyticsClient::new(
+        &env,
+        &env.register(mock_analytics::MockAnalytics, ()),
+    );
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_analytics(&analytics.address);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+
+    // Replacing a grace-period subscription
+    contract.subscribe(&user, &plan_id);
+    env.ledger().set_timestamp(31 * 86400);
+    assert_eq!(contract.check_subscription_status(&user), SubscriptionStatus::GracePeriod);
+    contract.subscribe(&user, &plan_id);
+    assert_eq!(analytics.get_total(&analytics::ACTIVE_SUBSCRIPTIONS), 1);
+
+    // Replacing a paused subscription
+    contract.pause_subscription(&user);
+    contract.subscribe(&user, &plan_id);
+    assert_eq!(analytics.get_total(&analytics::ACTIVE_SUBSCRIPTIONS), 1);
+    assert_eq!(contract.get_subscription(&user).unwrap().status, SubscriptionStatus::Active);
+}
+
+mod mock_registry {
+    use soroban_sdk::{contract, contractimpl, Env, Symbol, Val};
+
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_u32(env: Env, namespace: Symbol, key: Symbol, value: u32) {
+            env.storage().persistent().set(&(namespace, key), &value);
+        }
+
+        pub fn set(env: Env, namespace: Symbol, key: Symbol, value: Val) {
+            env.storage().persistent().set(&(namespace, key), &value);
+        }
+
+        pub fn get_u32(env: Env, namespace: Symbol, key: Symbol) -> Option<u32> {
+            env.storage().persistent().get(&(namespace, key))
+        }
+    }
+}
+
+#[test]
+fn test_registry_overrides_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+    let registry = mock_registry::MockRegistryClient::new(
+        &env,
+        &env.register(mock_registry::MockRegistry, ()),
+    );
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_registry(&registry.address);
+    registry.set_u32(&symbol_short!("subs"), &symbol_short!("grace"), &0);
+
+    let category_ids = Vec::from_array(&env, [1, 2, 3]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+
+    // A day past the end date is within the local 7-day grace period, but governance set it to 0
+    env.ledger().set_timestamp(31 * 86400);
+    assert_eq!(contract.check_subscription_status(&user), SubscriptionStatus::Expired);
+}
+
+#[test]
+fn test_registry_overrides_dunning_and_ignores_mismatched_types() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+    let registry = mock_registry::MockRegistryClient::new(
+        &env,
+        &env.register(mock_registry::MockRegistry, ()),
+    );
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_dunning_config(&2, &2);
+    contract.set_registry(&registry.address);
+
+    let ns = symbol_short!("subs");
+    registry.set(&ns, &symbol_short!("grace"), &true.into_val(&env));
+    registry.set_u32(&ns, &symbol_short!("retry_day"), &5);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(!contract.process_renewal(&user));
+    assert_eq!(contract.get_dunning_state(&user).unwrap().next_retry_at, 35 * 86400);
+
+    // The mistyped grace period is ignored, so the local 7 days still apply
+    env.ledger().set_timestamp(31 * 86400);
+    assert_eq!(contract.check_subscription_status(&user), SubscriptionStatus::PastDue);
+}
+
+#[test]
+fn test_subscribe_and_renew_with_alternative_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let usdc = create_token_contract(&env, &token_admin);
+    let usdc_client = token::Client::new(&env, &usdc.address);
+    let contract = create_subscription_contract(&env);
+
+    usdc.mint(&user, &1000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1, 2, 3]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    contract.set_plan_token_price(&plan_id, &usdc.address, &250);
+
+    contract.subscribe_with_token(&user, &plan_id, &usdc.address);
+    assert_eq!(usdc_client.balance(&user), 750);
+    assert_eq!(contract.get_subscription(&user).unwrap().payment_token, usdc.address);
+
+    // Renewals keep charging the recorded payment token
+    contract.renew_subscription(&user);
+    assert_eq!(usdc_client.balance(&user), 500);
+    assert_eq!(contract.get_treasury_balance(&usdc.address), 500);
+}
+
+#[test]
+#[should_panic(expected = "Token not accepted for plan")]
+fn test_subscribe_with_unaccepted_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let other = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe_with_token(&user, &plan_id, &other.address);
+}
+
+#[test]
+fn test_subscribe_with_native_xlm() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    // Stands in for the network's native XLM asset contract
+    let xlm = create_token_contract(&env, &token_admin);
+    let xlm_client = token::Client::new(&env, &xlm.address);
+    let contract = create_subscription_contract(&env);
+
+    xlm.mint(&user, &100_000_000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_native_token(&xlm.address);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    contract.set_plan_native_price(&plan_id, &50_000_000);
+
+    contract.subscribe_with_native(&user, &plan_id);
+    assert_eq!(xlm_client.balance(&user), 50_000_000);
+    assert_eq!(contract.get_subscription(&user).unwrap().payment_token, xlm.address);
+    assert_eq!(contract.get_native_token(), Some(xlm.address));
+}
+
+#[test]
+fn test_revenue_held_and_refunded_from_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &3000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+    assert_eq!(contract.get_payment_recipient(), contract.address);
+    assert_eq!(token_client.balance(&contract.address), 3000);
+    assert_eq!(token_client.balance(&admin), 0);
+
+    // Halfway through the term the user gets half back out of held revenue
+    env.ledger().set_timestamp(15 * 86400);
+    contract.cancel_subscription(&user);
+    assert_eq!(token_client.balance(&user), 8500);
+    assert_eq!(contract.get_treasury_balance(&token.address), 1500);
+
+    contract.withdraw_revenue(&token.address, &1500, &treasury);
+    assert_eq!(token_client.balance(&treasury), 1500);
+    assert_eq!(contract.get_treasury_balance(&token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient treasury balance")]
+fn test_withdraw_more_than_revenue() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.withdraw_revenue(&token.address, &1, &admin);
+}
+
+#[test]
+fn test_keeper_renews_from_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let broke_user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    token.mint(&broke_user, &1000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+    contract.subscribe(&broke_user, &plan_id);
+    token_client.approve(&user, &contract.address, &5000, &1000);
+
+    // Not yet inside the renewal window
+    let users = Vec::from_array(&env, [user.clone(), broke_user.clone()]);
+    assert_eq!(contract.process_due_renewals(&users), 0);
+
+    env.ledger().set_timestamp(29 * 86400 + 3600);
+    assert_eq!(contract.process_due_renewals(&users), 1);
+
+    let subscription = contract.get_subscription(&user).unwrap();
+    assert_eq!(subscription.end_date, 60 * 86400);
+    assert_eq!(token_client.balance(&user), 8000);
+    assert_eq!(token_client.allowance(&user, &contract.address), 4000);
+
+    // No allowance: the charge failed without reverting and the term is unchanged
+    let subscription = contract.get_subscription(&broke_user).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::PastDue);
+    assert_eq!(subscription.end_date, 30 * 86400);
+}
+
+#[test]
+#[should_panic(expected = "Renewal not due")]
+fn test_keeper_renewal_requires_auto_renew() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+    contract.set_auto_renew(&user, &false);
+
+    env.ledger().set_timestamp(30 * 86400);
+    contract.process_renewal(&user);
+}
+
+#[test]
+fn test_dunning_retries_then_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_dunning_config(&2, &2);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(!contract.process_renewal(&user));
+    assert_eq!(contract.check_subscription_status(&user), SubscriptionStatus::PastDue);
+
+    let state = contract.get_dunning_state(&user).unwrap();
+    assert_eq!(state.failed_attempts, 1);
+    assert_eq!(state.next_retry_at, 32 * 86400);
+
+    // Not retried before the interval has passed
+    let users = Vec::from_array(&env, [user.clone()]);
+    env.ledger().set_timestamp(31 * 86400);
+    assert_eq!(contract.process_due_renewals(&users), 0);
+
+    env.ledger().set_timestamp(32 * 86400);
+    assert!(!contract.process_renewal(&user));
+    assert_eq!(contract.get_dunning_state(&user).unwrap().failed_attempts, 2);
+
+    // Third failure exceeds max_retries and ends dunning
+    env.ledger().set_timestamp(34 * 86400);
+    assert!(!contract.process_renewal(&user));
+    assert_eq!(contract.check_subscription_status(&user), SubscriptionStatus::Expired);
+    assert!(contract.get_dunning_state(&user).is_none());
+    assert_eq!(token_client.balance(&user), 9000);
+}
+
+#[test]
+fn test_dunning_retry_recovers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(!contract.process_renewal(&user));
+
+    token_client.approve(&user, &contract.address, &1000, &1000);
+    env.ledger().set_timestamp(31 * 86400);
+    assert!(contract.process_renewal(&user));
+
+    let subscription = contract.get_subscription(&user).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert_eq!(subscription.end_date, 61 * 86400);
+    assert!(contract.get_dunning_state(&user).is_none());
+}
+
+#[test]
+fn test_two_step_subscription_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    let subscription_id = contract.subscribe(&user, &plan_id);
+    contract.add_family_member(&user, &member);
+
+    contract.initiate_transfer(&user, &new_wallet);
+    assert_eq!(contract.get_pending_transfer(&user), Some(new_wallet.clone()));
+    assert!(contract.has_category_access(&user, &1));
+
+    contract.accept_transfer(&new_wallet, &user);
+
+    let subscription = contract.get_subscription(&new_wallet).unwrap();
+    assert_eq!(subscription.subscription_id, subscription_id);
+    assert_eq!(subscription.user, new_wallet);
+    assert!(subscription.family_members.contains(&member));
+
+    assert!(contract.get_subscription(&user).is_none());
+    assert!(contract.get_pending_transfer(&user).is_none());
+    assert!(!contract.has_category_access(&user, &1));
+    assert!(contract.has_category_access(&new_wallet, &1));
+}
+
+#[test]
+#[should_panic(expected = "Transfer not for this address")]
+fn test_transfer_accepted_only_by_new_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+    contract.initiate_transfer(&user, &new_wallet);
+    contract.accept_transfer(&stranger, &user);
+}
+
+#[test]
+#[should_panic(expected = "Subscription cannot be transferred")]
+fn test_transfer_rejected_once_subscription_lapses() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+
+    contract.subscribe(&user, &plan_id);
+    contract.initiate_transfer(&user, &new_wallet);
+
+    // Offered while active, but cancelled before the new owner accepts
+    contract.cancel_subscription(&user);
+    contract.accept_transfer(&new_wallet, &user);
+}
+
+#[test]
+fn test_deprecated_plan_grandfathers_then_sunsets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    token.mint(&newcomer, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let legacy_plan = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    let successor_plan = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1500,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &legacy_plan);
+    contract.deprecate_plan(&legacy_plan, &Some(60 * 86400), &Some(successor_plan));
+
+    assert!(contract.try_subscribe(&newcomer, &legacy_plan).is_err());
+    assert!(contract.try_update_plan(&legacy_plan, &2000, &category_ids, &true).is_err());
+    let other_token = create_token_contract(&env, &token_admin);
+    assert!(contract
+        .try_set_plan_token_price(&legacy_plan, &other_token.address, &900)
+        .is_err());
+
+    // Everything but the price stays editable
+    let new_categories = Vec::from_array(&env, [1, 2]);
+    contract.update_plan(&legacy_plan, &1000, &new_categories, &true);
+    assert_eq!(contract.get_plan(&legacy_plan).unwrap().category_ids, new_categories);
+    assert!(contract.has_category_access(&user, &2));
+
+    // Before the sunset the existing subscriber renews at the original price
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), 8000);
+    assert_eq!(contract.get_subscription(&user).unwrap().plan_id, legacy_plan);
+
+    // After the sunset renewal moves them to the successor
+    env.ledger().set_timestamp(60 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), 6500);
+    assert_eq!(contract.get_subscription(&user).unwrap().plan_id, successor_plan);
+}
+
+#[test]
+fn test_grandfathered_renewal_charges_pinned_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+
+    contract.subscribe(&user, &plan_id);
+    contract.set_auto_renew(&user, &true);
+    token_client.approve(&user, &contract.address, &10000, &1000);
+
+    // The price goes up after the user signed up, then the plan is deprecated
+    contract.update_plan(&plan_id, &1500, &category_ids, &true);
+    contract.deprecate_plan(&plan_id, &None, &None);
+    assert_eq!(contract.get_required_allowance(&user), 1000);
+
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(contract.process_renewal(&user));
+    assert_eq!(token_client.balance(&user), 8000);
+    assert_eq!(contract.get_subscription(&user).unwrap().price_version, 1);
+
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), 7000);
+    assert_eq!(contract.get_pinned_price(&user), Some(1000));
+}
+
+#[test]
+fn test_usage_overage_billed_at_renewal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    let listings = symbol_short!("listings");
+    contract.set_meter_limit(&plan_id, &listings, &5, &50);
+    contract.set_metering_consumer(&consumer, &true);
+
+    contract.subscribe(&user, &plan_id);
+    contract.record_usage(&consumer, &user, &listings, &4);
+    assert_eq!(contract.record_usage(&consumer, &user, &listings, &4), 8);
+
+    // 3 units over the included 5 at 50 each
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), 10000 - 1000 - 1150);
+    assert_eq!(contract.get_usage(&user, &listings), 0);
+}
+
+#[test]
+fn test_overage_settled_under_plan_being_left() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let metered_plan = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+    let flat_plan = contract.create_plan(&SubscriptionTier::Monthly, &500, &30, &category_ids, &5);
+    let listings = symbol_short!("listings");
+    contract.set_meter_limit(&metered_plan, &listings, &5, &50);
+    contract.set_metering_consumer(&consumer, &true);
+
+    contract.subscribe(&user, &metered_plan);
+    contract.record_usage(&consumer, &user, &listings, &8);
+    contract.schedule_plan_change(&user, &flat_plan);
+
+    // New plan's price plus 3 units of overage on the metered plan being left
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), 10000 - 1000 - 650);
+    assert_eq!(contract.get_subscription(&user).unwrap().plan_id, flat_plan);
+    assert_eq!(contract.get_usage(&user, &listings), 0);
+}
+
+#[test]
+fn test_auto_renew_settles_overage_before_plan_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let metered_plan = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+    let flat_plan = contract.create_plan(&SubscriptionTier::Monthly, &500, &30, &category_ids, &5);
+    let listings = symbol_short!("listings");
+    contract.set_meter_limit(&metered_plan, &listings, &5, &50);
+    contract.set_metering_consumer(&consumer, &true);
+
+    contract.subscribe(&user, &metered_plan);
+    contract.record_usage(&consumer, &user, &listings, &8);
+    contract.schedule_plan_change(&user, &flat_plan);
+    assert_eq!(contract.get_required_allowance(&user), 650);
+
+    token_client.approve(&user, &contract.address, &650, &1000);
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(contract.process_renewal(&user));
+    assert_eq!(token_client.balance(&user), 10000 - 1000 - 650);
+    assert_eq!(contract.get_subscription(&user).unwrap().plan_id, flat_plan);
+    assert_eq!(contract.get_usage(&user, &listings), 0);
+}
+
+#[test]
+#[should_panic(expected = "Not an authorized consumer")]
+fn test_record_usage_requires_authorized_consumer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    contract.set_meter_limit(&plan_id, &symbol_short!("listings"), &5, &50);
+
+    contract.subscribe(&user, &plan_id);
+    contract.record_usage(&consumer, &user, &symbol_short!("listings"), &1);
+}
+
+#[test]
+fn test_family_members_share_owner_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1, 2]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Annual,
+        &10000,
+        &365,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&owner, &plan_id);
+    contract.add_family_member(&owner, &member1);
+    contract.add_family_member(&owner, &member2);
+
+    assert_eq!(contract.get_family_owner(&member1), Some(owner.clone()));
+    assert!(contract.has_category_access(&member1, &1));
+    assert!(contract.check_family_access(&member2, &2));
+    assert!(!contract.has_category_access(&member1, &3));
+
+    contract.remove_family_member(&owner, &member1);
+    assert!(contract.get_family_owner(&member1).is_none());
+    assert!(!contract.has_category_access(&member1, &1));
+
+    // Members lose access with the owner's subscription
+    contract.cancel_subscription(&owner);
+    assert!(!contract.has_category_access(&member2, &1));
+}
+
+#[test]
+fn test_pause_policy_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    contract.set_pause_policy(&plan_id, &10, &1);
+
+    contract.subscribe(&user, &plan_id);
+
+    // Pause with 20 days left and stay away well past the 10-day limit
+    env.ledger().set_timestamp(10 * 86400);
+    contract.pause_subscription(&user);
+
+    env.ledger().set_timestamp(25 * 86400);
+    assert_eq!(contract.check_subscription_status(&user), SubscriptionStatus::Active);
+
+    // The pause ended on day 20, so the remaining 20 days run to day 40
+    let subscription = contract.get_subscription(&user).unwrap();
+    assert_eq!(subscription.end_date, 40 * 86400);
+
+    // Only one pause per term
+    assert!(contract.try_pause_subscription(&user).is_err());
+}
+
+#[test]
+fn test_scheduled_downgrade_applies_at_renewal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let premium_plan = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &2000,
+        &30,
+        &Vec::from_array(&env, [1, 2]),
+        &5,
+    );
+    let basic_plan = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &Vec::from_array(&env, [1]),
+        &5,
+    );
+
+    contract.subscribe(&user, &premium_plan);
+    token_client.approve(&user, &contract.address, &5000, &1000);
+
+    env.ledger().set_timestamp(10 * 86400);
+    contract.schedule_plan_change(&user, &basic_plan);
+
+    // Nothing changes mid-period
+    assert_eq!(contract.get_subscription(&user).unwrap().plan_id, premium_plan);
+    assert!(contract.has_category_access(&user, &2));
+    assert_eq!(contract.get_scheduled_plan_change(&user), Some(basic_plan));
+
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(contract.process_renewal(&user));
+
+    let subscription = contract.get_subscription(&user).unwrap();
+    assert_eq!(subscription.plan_id, basic_plan);
+    assert_eq!(token_client.balance(&user), 7000);
+    assert!(contract.get_scheduled_plan_change(&user).is_none());
+    assert!(!contract.has_category_access(&user, &2));
+}
+
+#[test]
+fn test_immediate_change_drops_scheduled_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let basic_plan = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+    let premium_plan = contract.create_plan(&SubscriptionTier::Monthly, &2000, &30, &category_ids, &5);
+    let business_plan = contract.create_plan(&SubscriptionTier::Monthly, &3000, &30, &category_ids, &5);
+
+    contract.subscribe(&user, &premium_plan);
+    token_client.approve(&user, &contract.address, &10000, &1000);
+
+    contract.schedule_plan_change(&user, &basic_plan);
+    contract.change_plan(&user, &business_plan);
+    assert!(contract.get_scheduled_plan_change(&user).is_none());
+
+    // The renewal stays on the plan the user switched to
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(contract.process_renewal(&user));
+    assert_eq!(contract.get_subscription(&user).unwrap().plan_id, business_plan);
+}
+
+#[test]
+fn test_refund_policies() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    let no_refund = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&early, &3000);
+    token.mint(&late, &3000);
+    token.mint(&no_refund, &3000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let trial_plan = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &3000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    let final_plan = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &3000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    contract.set_refund_policy(&trial_plan, &RefundPolicy::FullWithinNDays(3));
+    contract.set_refund_policy(&final_plan, &RefundPolicy::None);
+    assert_eq!(contract.get_plan(&trial_plan).unwrap().refund_policy, RefundPolicy::FullWithinNDays(3));
+
+    contract.subscribe(&early, &trial_plan);
+    contract.subscribe(&late, &trial_plan);
+    contract.subscribe(&no_refund, &final_plan);
+
+    env.ledger().set_timestamp(2 * 86400);
+    contract.cancel_subscription(&early);
+    assert_eq!(token_client.balance(&early), 3000);
+
+    env.ledger().set_timestamp(4 * 86400);
+    contract.cancel_subscription(&late);
+    assert_eq!(token_client.balance(&late), 0);
+
+    contract.cancel_subscription(&no_refund);
+    assert_eq!(token_client.balance(&no_refund), 0);
+}
+
+#[test]
+fn test_scheduled_gift_delivery() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let gifter = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&gifter, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Annual,
+        &10000,
+        &365,
+        &category_ids,
+        &5,
+    );
+
+    env.ledger().set_timestamp(1000);
+    let message = String::from_str(&env, "Happy birthday!");
+    let gift_id = contract.gift_subscription(&gifter, &recipient, &plan_id, &message, &(10 * 86400));
+
+    let gift = contract.get_gift(&gift_id).unwrap();
+    assert_eq!(gift.message, message);
+    assert!(!gift.announced);
+
+    assert!(contract.try_claim_gift(&recipient, &gift_id).is_err());
+    assert!(contract.try_announce_gift(&gift_id).is_err());
+
+    env.ledger().set_timestamp(10 * 86400);
+    contract.announce_gift(&gift_id);
+    assert!(contract.get_gift(&gift_id).unwrap().announced);
+
+    contract.claim_gift(&recipient, &gift_id);
+    let subscription = contract.get_subscription(&recipient).unwrap();
+    assert_eq!(subscription.end_date, 375 * 86400);
+}
+
+mod mock_badge {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockBadge;
+
+    #[contractimpl]
+    impl MockBadge {
+        pub fn issue(env: Env, minter: Address, to: Address, _plan_id: u32, expires_at: u64) {
+            minter.require_auth();
+            env.storage().persistent().set(&to, &expires_at);
+        }
+
+        pub fn revoke(env: Env, minter: Address, holder: Address) {
+            minter.require_auth();
+            env.storage().persistent().remove(&holder);
+        }
+
+        pub fn expires_at(env: Env, holder: Address) -> Option<u64> {
+            env.storage().persistent().get(&holder)
+        }
+    }
+}
+
+#[test]
+fn test_membership_badge_follows_subscription() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+    let badge = mock_badge::MockBadgeClient::new(&env, &env.register(mock_badge::MockBadge, ()));
+
+    token.mint(&user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_membership_badge(&badge.address);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&user, &plan_id);
+    assert_eq!(badge.expires_at(&user), Some(30 * 86400));
+
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(badge.expires_at(&user), Some(60 * 86400));
+
+    contract.initiate_transfer(&user, &new_wallet);
+    contract.accept_transfer(&new_wallet, &user);
+    assert_eq!(badge.expires_at(&user), None);
+    assert_eq!(badge.expires_at(&new_wallet), Some(60 * 86400));
+
+    contract.cancel_subscription(&new_wallet);
+    assert_eq!(badge.expires_at(&new_wallet), None);
+}
+
+#[test]
+fn test_access_continues_through_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&owner, &plan_id);
+    contract.add_family_member(&owner, &member);
+
+    // Three days after the term ends, inside the 7-day grace period
+    env.ledger().set_timestamp(33 * 86400);
+    assert_eq!(contract.check_subscription_status(&owner), SubscriptionStatus::GracePeriod);
+    assert!(contract.has_category_access(&owner, &1));
+    assert!(contract.has_category_access(&member, &1));
+    assert!(!contract.has_category_access(&member, &2));
+
+    // Access ends with the grace period even before anyone updates the status
+    env.ledger().set_timestamp(38 * 86400);
+    assert!(!contract.has_category_access(&owner, &1));
+    assert!(!contract.has_category_access(&member, &1));
+    assert_eq!(contract.check_subscription_status(&owner), SubscriptionStatus::Expired);
+}
+
+#[test]
+fn test_family_member_access_resolves_through_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [4, 5]);
+    let plan_id = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&owner, &plan_id);
+    contract.add_family_member(&owner, &member);
+
+    assert!(contract.has_category_access(&member, &4));
+    assert!(contract.has_category_access(&member, &5));
+    assert!(!contract.has_category_access(&outsider, &4));
+
+    // Pausing the owner's subscription pauses the family's access too
+    contract.pause_subscription(&owner);
+    assert!(!contract.has_category_access(&member, &4));
+}
+
+#[test]
+fn test_subscriber_listing() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let monthly = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &1000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    let annual = contract.create_plan(
+        &SubscriptionTier::Annual,
+        &10000,
+        &365,
+        &category_ids,
+        &5,
+    );
+
+    let mut users = Vec::new(&env);
+    for i in 0..5 {
+        let user = Address::generate(&env);
+        token.mint(&user, &20000);
+        contract.subscribe(&user, &if i < 3 { monthly } else { annual });
+        users.push_back(user);
+    }
+
+    assert_eq!(contract.list_subscribers(&0, &10).len(), 5);
+    assert_eq!(contract.list_subscribers(&2, &2), users.slice(2..4));
+    assert_eq!(contract.list_subscribers(&10, &2).len(), 0);
+    assert_eq!(contract.list_subscribers_by_plan(&monthly, &0, &10), users.slice(0..3));
+
+    // Cancellations and plan changes keep the indexes current
+    contract.cancel_subscription(&users.get(0).unwrap());
+    contract.change_plan(&users.get(1).unwrap(), &annual);
+    assert_eq!(contract.list_subscribers(&0, &10).len(), 4);
+    assert_eq!(contract.list_subscribers_by_plan(&monthly, &0, &10).len(), 1);
+    assert_eq!(contract.list_subscribers_by_plan(&annual, &0, &10).len(), 3);
+
+    // Lapsed subscriptions drop out once they expire
+    env.ledger().set_timestamp(40 * 86400);
+    contract.check_subscription_status(&users.get(2).unwrap());
+    assert_eq!(contract.list_subscribers_by_plan(&monthly, &0, &10).len(), 0);
+    assert_eq!(contract.list_subscribers(&0, &10).len(), 3);
+}
+
+#[test]
+fn test_revenue_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&alice, &10000);
+    token.mint(&bob, &10000);
+    token.mint(&carol, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let monthly = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &3000,
+        &30,
+        &category_ids,
+        &5,
+    );
+    let weekly = contract.create_plan(
+        &SubscriptionTier::Monthly,
+        &500,
+        &7,
+        &category_ids,
+        &5,
+    );
+
+    contract.subscribe(&alice, &monthly);
+    contract.subscribe(&bob, &monthly);
+    contract.subscribe(&carol, &weekly);
+
+    // Alice cancels halfway and gets 1500 back
+    env.ledger().set_timestamp(15 * 86400);
+    contract.cancel_subscription(&alice);
+
+    // Carol's weekly plan has lapsed past its grace period
+    contract.check_subscription_status(&carol);
+
+    let stats = contract.get_revenue_stats();
+    assert_eq!(stats.revenue, 6500);
+    assert_eq!(stats.refunds, 1500);
+    assert_eq!(stats.subscriptions, 3);
+    assert_eq!(stats.active_subscribers, 1);
+    assert_eq!(stats.cancellations, 1);
+    assert_eq!(stats.expirations, 1);
+
+    let monthly_stats = contract.get_plan_stats(&monthly);
+    assert_eq!(monthly_stats.revenue, 6000);
+    assert_eq!(monthly_stats.refunds, 1500);
+    assert_eq!(monthly_stats.active_subscribers, 1);
+    assert_eq!(monthly_stats.cancellations, 1);
+
+    let weekly_stats = contract.get_plan_stats(&weekly);
+    assert_eq!(weekly_stats.revenue, 500);
+    assert_eq!(weekly_stats.active_subscribers, 0);
+    assert_eq!(weekly_stats.expirations, 1);
+}
+
+#[test]
+fn test_sweep_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let monthly_user = Address::generate(&env);
+    let weekly_user = Address::generate(&env);
+    let annual_user = Address::generate(&env);
+    let paused_user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&monthly_user, &10000);
+    token.mint(&weekly_user, &10000);
+    token.mint(&annual_user, &10000);
+    token.mint(&paused_user, &10000);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let monthly = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+    let weekly = contract.create_plan(&SubscriptionTier::Monthly, &300, &7, &category_ids, &5);
+    let annual = contract.create_plan(&SubscriptionTier::Annual, &5000, &365, &category_ids, &5);
+
+    contract.subscribe(&monthly_user, &monthly);
+    contract.subscribe(&weekly_user, &weekly);
+    contract.subscribe(&annual_user, &annual);
+
+    let pausable = contract.create_plan(&SubscriptionTier::Annual, &5000, &365, &category_ids, &5);
+    contract.set_pause_policy(&pausable, &10, &1);
+    contract.subscribe(&paused_user, &pausable);
+    contract.pause_subscription(&paused_user);
+
+    let users = Vec::from_array(
+        &env,
+        [
+            monthly_user.clone(),
+            weekly_user.clone(),
+            annual_user.clone(),
+            paused_user.clone(),
+            stranger,
+        ],
+    );
+
+    // The overdue pause is ended but not counted
+    env.ledger().set_timestamp(31 * 86400);
+    assert_eq!(contract.sweep_expired(&users), 2);
+
+    assert_eq!(contract.get_subscription(&monthly_user).unwrap().status, SubscriptionStatus::GracePeriod);
+    assert_eq!(contract.get_subscription(&weekly_user).unwrap().status, SubscriptionStatus::Expired);
+    assert_eq!(contract.get_subscription(&annual_user).unwrap().status, SubscriptionStatus::Active);
+    assert_eq!(contract.get_subscription(&paused_user).unwrap().status, SubscriptionStatus::Active);
+
+    // Nothing left to change on a second pass
+    assert_eq!(contract.sweep_expired(&users), 0);
+}
+
+#[test]
+fn test_renewal_due_notified_once_per_term() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+    contract.subscribe(&user, &plan_id);
+
+    let subscription = contract.get_subscription(&user).unwrap();
+    let notified = || {
+        env.as_contract(&contract.address, || {
+            env.storage()
+                .persistent()
+                .get::<DataKey, u64>(&DataKey::RenewalNotified(subscription.subscription_id))
+        })
+    };
+
+    // Outside the renewal window nothing is emitted
+    env.ledger().set_timestamp(28 * 86400);
+    contract.check_subscription_status(&user);
+    assert_eq!(notified(), None);
+
+    env.ledger().set_timestamp(subscription.end_date - 3600);
+    contract.check_subscription_status(&user);
+    assert_eq!(notified(), Some(subscription.end_date));
+
+    // Renewing moves the due date, so the next window notifies again
+    contract.renew_subscription(&user);
+    let renewed = contract.get_subscription(&user).unwrap();
+    env.ledger().set_timestamp(renewed.end_date - 3600);
+    contract.check_subscription_status(&user);
+    assert_eq!(notified(), Some(renewed.end_date));
+}
+
+#[test]
+fn test_team_plan_seats() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.set_team_plan(&plan_id, &10);
+
+    // Charged per seat
+    contract.subscribe_team(&owner, &plan_id, &2);
+    assert_eq!(token_client.balance(&owner), 8000);
+
+    contract.assign_seat(&owner, &alice);
+    contract.assign_seat(&owner, &bob);
+    assert!(contract.has_category_access(&alice, &1));
+    assert!(contract.has_category_access(&bob, &1));
+    assert_eq!(contract.get_seat_owner(&alice), Some(owner.clone()));
+    assert!(contract.try_assign_seat(&owner, &carol).is_err());
+
+    // A freed seat can be reassigned
+    contract.unassign_seat(&owner, &bob);
+    assert!(!contract.has_category_access(&bob, &1));
+    contract.assign_seat(&owner, &carol);
+    assert!(contract.has_category_access(&carol, &1));
+
+    // Renewals scale with the seat count too
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&owner);
+    assert_eq!(token_client.balance(&owner), 6000);
+}
+
+#[test]
+fn test_seats_lapse_on_plan_without_seats() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let team_plan = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.set_team_plan(&team_plan, &10);
+    let solo_plan = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+
+    contract.subscribe_team(&owner, &team_plan, &1);
+    contract.assign_seat(&owner, &alice);
+    assert!(contract.check_seat_access(&alice, &1));
+
+    contract.change_plan(&owner, &solo_plan);
+    assert!(!contract.check_seat_access(&alice, &1));
+    assert!(!contract.has_category_access(&alice, &1));
+    assert!(contract.has_category_access(&owner, &1));
+}
+
+#[test]
+#[should_panic(expected = "Plan is not a team plan")]
+fn test_seats_require_team_plan() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.subscribe_team(&owner, &plan_id, &3);
+}
+
+#[test]
+fn test_category_bundles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let bundle_id = contract.create_bundle(&String::from_str(&env, "Music"), &Vec::from_array(&env, [10, 11]));
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &Vec::from_array(&env, [1]), &0);
+    contract.set_plan_bundles(&plan_id, &Vec::from_array(&env, [bundle_id]));
+    contract.subscribe(&user, &plan_id);
+
+    assert!(contract.has_category_access(&user, &1));
+    assert!(contract.has_category_access(&user, &10));
+    assert!(!contract.has_category_access(&user, &12));
+
+    // Updating the bundle changes access for every plan that includes it
+    contract.update_bundle(&bundle_id, &Vec::from_array(&env, [11, 12]));
+    assert!(!contract.has_category_access(&user, &10));
+    assert!(contract.has_category_access(&user, &12));
+    assert_eq!(contract.get_bundle(&bundle_id).unwrap().category_ids.len(), 2);
+
+    assert!(contract.try_set_plan_bundles(&plan_id, &Vec::from_array(&env, [99])).is_err());
+}
+
+#[test]
+fn test_change_plan_charges_prorated_difference() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let basic = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    let premium = contract.create_plan(&SubscriptionTier::Monthly, &3000, &30, &category_ids, &0);
+
+    contract.subscribe(&user, &basic);
+    let end_date = contract.get_subscription(&user).unwrap().end_date;
+    assert_eq!(token_client.balance(&user), 9000);
+
+    // Halfway through the term: pay half the price difference (1500 - 500)
+    env.ledger().set_timestamp(15 * 86400);
+    contract.change_plan(&user, &premium);
+    assert_eq!(token_client.balance(&user), 8000);
+    assert_eq!(contract.get_subscription(&user).unwrap().end_date, end_date);
+
+    // A quarter of the term left: refund a quarter of the difference (750 - 250)
+    env.ledger().set_timestamp(22 * 86400 + 43200);
+    contract.change_plan(&user, &basic);
+    assert_eq!(token_client.balance(&user), 8500);
+    assert_eq!(contract.get_plan_stats(&premium).refunds, 500);
+    assert_eq!(contract.get_plan_stats(&basic).refunds, 0);
+
+    let subscription = contract.get_subscription(&user).unwrap();
+    assert_eq!(subscription.plan_id, basic);
+    assert_eq!(subscription.end_date, end_date);
+}
+
+#[test]
+fn test_change_plan_after_renewal_uses_plan_term() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let basic = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    let premium = contract.create_plan(&SubscriptionTier::Monthly, &3000, &30, &category_ids, &0);
+
+    contract.subscribe(&user, &basic);
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), 8000);
+
+    // Ten days into the second term, two thirds of it remain
+    env.ledger().set_timestamp(40 * 86400);
+    contract.change_plan(&user, &premium);
+    assert_eq!(token_client.balance(&user), 8000 - (2000 - 666));
+}
+
+#[test]
+fn test_prepay_discount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &30000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.set_prepay_discount(&plan_id, &12, &1500);
+    assert_eq!(contract.get_prepay_discount(&plan_id, &12), 1500);
+
+    // Twelve months upfront at 15% off
+    contract.subscribe_prepaid(&user, &plan_id, &12);
+    assert_eq!(token_client.balance(&user), 30000 - 10200);
+    assert_eq!(contract.get_subscription(&user).unwrap().end_date, 360 * 86400);
+
+    // Lengths without a configured discount pay full price
+    env.ledger().set_timestamp(360 * 86400);
+    contract.renew_prepaid(&user, &3);
+    assert_eq!(token_client.balance(&user), 30000 - 10200 - 3000);
+    assert_eq!(contract.get_subscription(&user).unwrap().end_date, 450 * 86400);
+}
+
+#[test]
+fn test_prepaid_cancel_refunds_what_was_paid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &20000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.set_prepay_discount(&plan_id, &12, &1000);
+
+    contract.subscribe_prepaid(&user, &plan_id, &12);
+    assert_eq!(token_client.balance(&user), 20000 - 10800);
+
+    // Cancelling straight away returns the whole prepayment, not one term
+    contract.cancel_subscription(&user);
+    assert_eq!(token_client.balance(&user), 20000);
+    assert_eq!(contract.get_treasury_balance(&token.address), 0);
+}
+
+#[test]
+fn test_discounted_prepay_downgrade_refund_bounded_by_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &20000);
+    token.mint(&other, &20000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let premium = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    let basic = contract.create_plan(&SubscriptionTier::Monthly, &100, &30, &category_ids, &0);
+    contract.set_prepay_discount(&premium, &12, &5000);
+
+    // Someone else's payment sits in the treasury and must not fund this user's refunds
+    contract.subscribe_prepaid(&other, &premium, &12);
+    contract.subscribe_prepaid(&user, &premium, &12);
+    assert_eq!(token_client.balance(&user), 20000 - 6000);
+
+    // Twelve basic terms are worth 1200, so 4800 of the 6000 paid comes back
+    contract.change_plan(&user, &basic);
+    assert_eq!(token_client.balance(&user), 20000 - 1200);
+    assert_eq!(contract.get_treasury_balance(&token.address), 6000 + 1200);
+
+    // Halfway through, a prorated cancel returns half of what is left
+    env.ledger().set_timestamp(180 * 86400);
+    contract.cancel_subscription(&user);
+    assert_eq!(token_client.balance(&user), 20000 - 600);
+    assert_eq!(contract.get_treasury_balance(&token.address), 6000 + 600);
+    assert_eq!(token_client.balance(&contract.address), 6000 + 600);
+}
+
+mod mock_referral {
+    use crate::referral::Attribution;
+    use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env, Symbol};
+
+    #[derive(Clone)]
+    #[contracttype]
+    pub enum Key {
+        Owner(Symbol),
+        Attribution(Address),
+        Earned(Address),
+    }
+
+    #[contract]
+    pub struct MockReferral;
+
+    #[contractimpl]
+    impl MockReferral {
+        pub fn register_code(env: Env, owner: Address, code: Symbol) {
+            env.storage().persistent().set(&Key::Owner(code), &owner);
+        }
+
+        pub fn attribute(env: Env, caller: Address, user: Address, code: Symbol) {
+            caller.require_auth();
+            let referrer: Address = env.storage().persistent().get(&Key::Owner(code.clone())).unwrap();
+            let attribution = Attribution {
+                code,
+                referrer,
+                consumer: caller,
+                attributed_at: env.ledger().timestamp(),
+            };
+            env.storage().persistent().set(&Key::Attribution(user), &attribution);
+        }
+
+        /// Pulls the pool, keeps 80% for the direct referrer and returns the rest,
+        /// as with an 80/20 split where only the first level is filled
+        pub fn accrue(env: Env, consumer: Address, user: Address, token: Address, amount: i128) -> i128 {
+            consumer.require_auth();
+            let contract = env.current_contract_address();
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&consumer, &contract, &amount);
+
+            let attribution: Attribution = env.storage().persistent().get(&Key::Attribution(user)).unwrap();
+            let accrued = amount * 8000 / 10000;
+            token_client.transfer(&contract, &consumer, &(amount - accrued));
+
+            let earned: i128 = env.storage().persistent().get(&Key::Earned(attribution.referrer.clone())).unwrap_or(0);
+            env.storage().persistent().set(&Key::Earned(attribution.referrer), &(earned + accrued));
+            accrued
+        }
+
+        pub fn get_attribution(env: Env, user: Address) -> Option<Attribution> {
+            env.storage().persistent().get(&Key::Attribution(user))
+        }
+
+        pub fn get_earned(env: Env, referrer: Address) -> i128 {
+            env.storage().persistent().get(&Key::Earned(referrer)).unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_referral_rewards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+    let referral = mock_referral::MockReferralClient::new(
+        &env,
+        &env.register(mock_referral::MockReferral, ()),
+    );
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_referral_config(&referral.address, &1000, &2);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+
+    let code = symbol_short!("FRIEND");
+    referral.register_code(&referrer, &code);
+    contract.subscribe_with_referral(&user, &plan_id, &code);
+
+    assert_eq!(referral.get_attribution(&user).unwrap().referrer, referrer);
+    assert_eq!(referral.get_earned(&referrer), 80);
+
+    // The first renewal is rewarded, later ones are not
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    env.ledger().set_timestamp(60 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(referral.get_earned(&referrer), 160);
+
+    // Shares the referral contract could not place come back to the treasury
+    assert_eq!(token_client.balance(&referral.address), 160);
+    assert_eq!(contract.get_treasury_balance(&token.address), 3000 - 160);
+    assert_eq!(token_client.balance(&contract.address), 3000 - 160);
+}
+
+#[test]
+fn test_referral_rewards_do_not_follow_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+    let referral = mock_referral::MockReferralClient::new(
+        &env,
+        &env.register(mock_referral::MockReferral, ()),
+    );
+
+    token.mint(&user, &10000);
+    token.mint(&new_wallet, &10000);
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_referral_config(&referral.address, &1000, &12);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+
+    let code = symbol_short!("FRIEND");
+    referral.register_code(&referrer, &code);
+    contract.subscribe_with_referral(&user, &plan_id, &code);
+    assert_eq!(referral.get_earned(&referrer), 80);
+
+    contract.initiate_transfer(&user, &new_wallet);
+    contract.accept_transfer(&new_wallet, &user);
+
+    // The new owner was never referred, so their renewals earn the old referrer nothing
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&new_wallet);
+    assert_eq!(referral.get_earned(&referrer), 80);
+}
+
+#[test]
+#[should_panic(expected = "Referrals are for new subscribers")]
+fn test_referral_rejects_existing_subscriber() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+    let referral = mock_referral::MockReferralClient::new(
+        &env,
+        &env.register(mock_referral::MockReferral, ()),
+    );
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_referral_config(&referral.address, &1000, &2);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+
+    let code = symbol_short!("FRIEND");
+    referral.register_code(&referrer, &code);
+    contract.subscribe(&user, &plan_id);
+    contract.cancel_subscription(&user);
+    contract.subscribe_with_referral(&user, &plan_id, &code);
+}
+
+#[test]
+fn test_suspend_subscription() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &5);
+    contract.subscribe(&user, &plan_id);
+    contract.add_family_member(&user, &member);
+
+    contract.suspend_subscription(&user, &String::from_str(&env, "chargeback"));
+    assert!(!contract.has_category_access(&user, &1));
+    assert!(!contract.has_category_access(&member, &1));
+    assert_eq!(contract.get_suspension(&user).unwrap().reason, String::from_str(&env, "chargeback"));
+    assert!(contract.try_renew_subscription(&user).is_err());
+    assert!(contract.try_cancel_subscription(&user).is_err());
+
+    // The record survives the hold
+    assert_eq!(contract.get_subscription(&user).unwrap().status, SubscriptionStatus::Active);
+
+    contract.unsuspend_subscription(&user);
+    assert!(contract.get_suspension(&user).is_none());
+    assert!(contract.has_category_access(&user, &1));
+    assert!(contract.has_category_access(&member, &1));
+}
+
+#[test]
+fn test_suspended_subscription_skipped_by_keeper() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.subscribe(&user, &plan_id);
+    token_client.approve(&user, &contract.address, &5000, &1000);
+
+    contract.suspend_subscription(&user, &String::from_str(&env, "fraud review"));
+
+    env.ledger().set_timestamp(30 * 86400);
+    assert_eq!(contract.process_due_renewals(&Vec::from_array(&env, [user.clone()])), 0);
+    assert!(contract.try_process_renewal(&user).is_err());
+}
+
+#[test]
+fn test_suspension_blocks_pause_and_plan_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let basic = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    let premium = contract.create_plan(&SubscriptionTier::Monthly, &2000, &30, &category_ids, &0);
+    contract.subscribe(&user, &basic);
+
+    contract.suspend_subscription(&user, &String::from_str(&env, "chargeback"));
+    assert!(contract.try_pause_subscription(&user).is_err());
+    assert!(contract.try_schedule_plan_change(&user, &premium).is_err());
+    assert!(contract.try_set_auto_renew(&user, &false).is_err());
+    assert!(contract.get_scheduled_plan_change(&user).is_none());
+    assert!(contract.get_subscription(&user).unwrap().auto_renew);
+}
+
+#[test]
+fn test_suspension_blocks_family_seat_and_transfer_management() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let team_owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+    token.mint(&team_owner, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let family = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &3);
+    let premium = contract.create_plan(&SubscriptionTier::Monthly, &2000, &30, &category_ids, &3);
+    let team = contract.create_plan(&SubscriptionTier::Monthly, &500, &30, &category_ids, &0);
+    contract.set_team_plan(&team, &5);
+
+    contract.subscribe(&owner, &family);
+    contract.add_family_member(&owner, &member);
+    contract.schedule_plan_change(&owner, &premium);
+    contract.initiate_transfer(&owner, &Address::generate(&env));
+    contract.pause_subscription(&owner);
+
+    contract.subscribe_team(&team_owner, &team, &2);
+    contract.assign_seat(&team_owner, &holder);
+
+    contract.suspend_subscription(&owner, &String::from_str(&env, "chargeback"));
+    contract.suspend_subscription(&team_owner, &String::from_str(&env, "chargeback"));
+
+    assert!(contract.try_resume_subscription(&owner).is_err());
+    assert!(contract.try_cancel_scheduled_plan_change(&owner).is_err());
+    assert!(contract.try_cancel_transfer(&owner).is_err());
+    assert!(contract.try_add_family_member(&owner, &Address::generate(&env)).is_err());
+    assert!(contract.try_remove_family_member(&owner, &member).is_err());
+    assert!(contract.try_assign_seat(&team_owner, &Address::generate(&env)).is_err());
+    assert!(contract.try_unassign_seat(&team_owner, &holder).is_err());
+
+    assert_eq!(contract.get_subscription(&owner).unwrap().status, SubscriptionStatus::Paused);
+    assert_eq!(contract.get_scheduled_plan_change(&owner), Some(premium));
+    assert_eq!(contract.get_subscription(&owner).unwrap().family_members.len(), 1);
+    assert_eq!(contract.get_subscription(&team_owner).unwrap().seat_members.len(), 1);
+}
+
+#[test]
+fn test_suspended_member_loses_shared_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let team_owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&owner, &10000);
+    token.mint(&team_owner, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let family = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &3);
+    let team = contract.create_plan(&SubscriptionTier::Monthly, &500, &30, &category_ids, &0);
+    contract.set_team_plan(&team, &5);
+
+    contract.subscribe(&owner, &family);
+    contract.add_family_member(&owner, &member);
+    contract.subscribe_team(&team_owner, &team, &2);
+    contract.assign_seat(&team_owner, &holder);
+
+    contract.suspend_subscription(&member, &String::from_str(&env, "chargeback"));
+    contract.suspend_subscription(&holder, &String::from_str(&env, "chargeback"));
+
+    // The hold follows the member onto subscriptions they share; the owners are unaffected
+    assert!(!contract.check_family_access(&member, &1));
+    assert!(!contract.check_seat_access(&holder, &1));
+    assert!(!contract.has_category_access(&member, &1));
+    assert!(!contract.has_category_access(&holder, &1));
+    assert!(contract.has_category_access(&owner, &1));
+    assert!(contract.has_category_access(&team_owner, &1));
+
+    contract.unsuspend_subscription(&member);
+    contract.unsuspend_subscription(&holder);
+    assert!(contract.check_family_access(&member, &1));
+    assert!(contract.check_seat_access(&holder, &1));
+}
+
+#[test]
+fn test_suspension_outlives_lapsed_subscription() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let gifter = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    token.mint(&gifter, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.subscribe(&user, &plan_id);
+    contract.suspend_subscription(&user, &String::from_str(&env, "chargeback"));
+
+    // Letting the subscription lapse does not shake off the hold
+    env.ledger().set_timestamp(40 * 86400);
+    assert_eq!(contract.check_subscription_status(&user), SubscriptionStatus::Expired);
+    assert!(contract.try_subscribe(&user, &plan_id).is_err());
+
+    let gift_id = contract.gift_subscription(&gifter, &user, &plan_id, &String::from_str(&env, "Hi"), &0);
+    assert!(contract.try_claim_gift(&user, &gift_id).is_err());
+    assert!(contract.get_suspension(&user).is_some());
+
+    contract.unsuspend_subscription(&user);
+    contract.claim_gift(&user, &gift_id);
+    assert!(contract.has_category_access(&user, &1));
+}
+
+#[test]
+fn test_required_allowance_covers_next_renewal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let basic = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    let premium = contract.create_plan(&SubscriptionTier::Monthly, &2500, &30, &category_ids, &0);
+
+    contract.subscribe(&user, &basic);
+    assert_eq!(contract.get_required_allowance(&user), 1000);
+
+    // A scheduled change is billed at the new plan's price
+    contract.schedule_plan_change(&user, &premium);
+    let required = contract.get_required_allowance(&user);
+    assert_eq!(required, 2500);
+
+    token_client.approve(&user, &contract.address, &required, &1000);
+    env.ledger().set_timestamp(30 * 86400);
+    assert!(contract.process_renewal(&user));
+    assert_eq!(token_client.balance(&user), 10000 - 1000 - 2500);
+    assert_eq!(token_client.allowance(&user, &contract.address), 0);
+
+    contract.set_auto_renew(&user, &false);
+    assert_eq!(contract.get_required_allowance(&user), 0);
+}
+
+#[test]
+fn test_price_change_keeps_paid_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.subscribe(&user, &plan_id);
+    assert_eq!(contract.get_subscription(&user).unwrap().price_version, 1);
+
+    env.ledger().set_timestamp(10 * 86400);
+    contract.update_plan(&plan_id, &3000, &category_ids, &true);
+
+    let history = contract.get_price_history(&plan_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap(), PriceVersion {
+            version: 2,
+            price: 3000,
+            token_prices: Map::new(&env),
+            effective_at: 10 * 86400,
+        });
+
+    // Halfway through the term the refund is half of what was paid, not of the new price
+    env.ledger().set_timestamp(15 * 86400);
+    contract.cancel_subscription(&user);
+    assert_eq!(token_client.balance(&user), 9500);
+}
+
+#[test]
+fn test_renewal_pins_current_price_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &10000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.subscribe(&user, &plan_id);
+
+    // Unchanged prices don't add versions
+    contract.update_plan(&plan_id, &1000, &category_ids, &true);
+    assert_eq!(contract.get_price_history(&plan_id).len(), 1);
+
+    contract.update_plan(&plan_id, &1200, &category_ids, &true);
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(contract.get_subscription(&user).unwrap().price_version, 2);
+}
+
+#[test]
+fn test_token_prices_are_versioned() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let usdc = create_token_contract(&env, &token_admin);
+    let usdc_client = token::Client::new(&env, &usdc.address);
+    let contract = create_subscription_contract(&env);
+
+    usdc.mint(&user, &1000);
+    contract.initialize(&admin, &token.address, &7);
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.set_plan_token_price(&plan_id, &usdc.address, &250);
+    contract.subscribe_with_token(&user, &plan_id, &usdc.address);
+    assert_eq!(contract.get_subscription(&user).unwrap().price_version, 2);
+
+    // Re-setting the same price doesn't add a version
+    contract.set_plan_token_price(&plan_id, &usdc.address, &250);
+    assert_eq!(contract.get_price_history(&plan_id).len(), 2);
+
+    env.ledger().set_timestamp(10 * 86400);
+    contract.set_plan_token_price(&plan_id, &usdc.address, &400);
+
+    let history = contract.get_price_history(&plan_id);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(1).unwrap().token_prices.get(usdc.address.clone()), Some(250));
+    assert_eq!(history.get(2).unwrap().token_prices.get(usdc.address.clone()), Some(400));
+
+    // The current term stays pinned to the price it was bought at
+    assert_eq!(contract.get_pinned_price(&user), Some(250));
+
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(usdc_client.balance(&user), 350);
+    assert_eq!(contract.get_subscription(&user).unwrap().price_version, 3);
+    assert_eq!(contract.get_pinned_price(&user), Some(400));
+
+    contract.remove_plan_token_price(&plan_id, &usdc.address);
+    assert_eq!(contract.get_price_history(&plan_id).get(3).unwrap().token_prices.len(), 0);
+    assert_eq!(contract.get_pinned_price(&user), Some(400));
+}
+
+#[test]
+fn test_loyalty_streak_discount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &20000);
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_loyalty_schedule(&Vec::from_array(
+        &env,
+        [
+            LoyaltyTier { min_renewals: 2, discount_bps: 500 },
+            LoyaltyTier { min_renewals: 3, discount_bps: 1000 },
+        ],
+    ));
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.subscribe(&user, &plan_id);
+
+    let status = contract.get_loyalty_status(&user);
+    assert_eq!(status, LoyaltyStatus { streak: 0, discount_bps: 0, next_tier_at: Some(2) });
+
+    // Two full-price renewals, then 5% off, then 10% off
+    let mut balance = 19000;
+    for (term, price) in [(1u64, 1000), (2, 1000), (3, 950), (4, 900)] {
+        env.ledger().set_timestamp(term * 30 * 86400);
+        contract.renew_subscription(&user);
+        balance -= price;
+        assert_eq!(token_client.balance(&user), balance);
+    }
+    assert_eq!(contract.get_loyalty_status(&user), LoyaltyStatus { streak: 4, discount_bps: 1000, next_tier_at: None });
+
+    // Letting the subscription expire breaks the streak
+    env.ledger().set_timestamp(200 * 86400);
+    contract.check_subscription_status(&user);
+    assert_eq!(contract.get_loyalty_status(&user).streak, 0);
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), balance - 1000);
+    assert_eq!(contract.get_loyalty_status(&user).streak, 1);
+}
+
+#[test]
+fn test_full_refund_returns_discounted_charge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let contract = create_subscription_contract(&env);
+
+    token.mint(&user, &20000);
+    token.mint(&other, &20000);
+    contract.initialize(&admin, &token.address, &7);
+    contract.set_loyalty_schedule(&Vec::from_array(
+        &env,
+        [LoyaltyTier { min_renewals: 1, discount_bps: 5000 }],
+    ));
+
+    let category_ids = Vec::from_array(&env, [1]);
+    let plan_id = contract.create_plan(&SubscriptionTier::Monthly, &1000, &30, &category_ids, &0);
+    contract.set_refund_policy(&plan_id, &RefundPolicy::FullWithinNDays(3));
+    contract.subscribe(&other, &plan_id);
+    contract.subscribe(&user, &plan_id);
+
+    env.ledger().set_timestamp(30 * 86400);
+    contract.renew_subscription(&user);
+    env.ledger().set_timestamp(60 * 86400);
+    contract.renew_subscription(&user);
+    assert_eq!(token_client.balance(&user), 20000 - 2500);
+
+    // Only the 500 charged for the discounted renewal comes back, not the list price
+    env.ledger().set_timestamp(61 * 86400);
+    contract.cancel_subscription(&user);
+    assert_eq!(token_client.balance(&user), 20000 - 2000);
+    assert_eq!(contract.get_treasury_balance(&token.address), 1000 + 2000);
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    contract.initialize(&admin, &token.address, &7);
+
+    contract.propose_admin(&new_admin);
+    assert_eq!(contract.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(contract.get_admin(), admin);
+
+    contract.accept_admin();
+    assert_eq!(contract.get_admin(), new_admin);
+    assert_eq!(contract.get_pending_admin(), None);
+
+    assert!(contract.try_accept_admin().is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_accept_admin_requires_proposed_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let contract = create_subscription_contract(&env);
+
+    contract.initialize(&admin, &token.address, &7);
+    contract.propose_admin(&new_admin);
+
+    // Only the current admin signs; the proposed admin never accepted
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &contract.address,
+            fn_name: "accept_admin",
+            args: ().into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    contract.accept_admin();
+}
+
+mod properties {
+    use crate::testutils::{create_simple_plan, create_subscription_contract};
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+    use test_utils::{create_token, set_time, setup_env, DAY};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Cancelling a prepaid, discounted subscription at any point never refunds more than
+        /// was paid, and the treasury always matches the tokens the contract holds.
+        #[test]
+        fn prop_prepaid_refund_bounded_by_payment(
+            price in 1i128..1_000_000_000,
+            duration_days in 1u32..365,
+            periods in 1u32..5,
+            discount_bps in 0u32..10_000,
+            cancel_at in 0u64..2_000 * DAY,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            let client = create_subscription_contract(&env, &token.address);
+            let plan_id = create_simple_plan(&env, &client, price, duration_days);
+            if periods > 1 {
+                client.set_prepay_discount(&plan_id, &periods, &discount_bps);
+            }
+
+            let user = Address::generate(&env);
+            let funds = price * periods as i128;
+            token.mint(&user, funds);
+            set_time(&env, 0);
+            client.subscribe_prepaid(&user, &plan_id, &periods);
+            let paid = funds - token.balance(&user);
+
+            set_time(&env, cancel_at);
+            client.cancel_subscription(&user);
+
+            let refunded = token.balance(&user) - (funds - paid);
+            prop_assert!(refunded >= 0 && refunded <= paid);
+            prop_assert_eq!(token.balance(&client.address), paid - refunded);
+            prop_assert_eq!(client.get_treasury_balance(&token.address), token.balance(&client.address));
+        }
+
+        /// Any mid-term plan change followed by a cancellation returns at most what the user
+        /// paid across both plans, and the treasury stays in step with the contract's balance.
+        #[test]
+        fn prop_plan_change_refunds_bounded_by_payments(
+            old_price in 1i128..1_000_000_000,
+            new_price in 1i128..1_000_000_000,
+            old_days in 1u32..365,
+            new_days in 1u32..365,
+            change_at in 0u64..400 * DAY,
+            cancel_delay in 0u64..400 * DAY,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            let client = create_subscription_contract(&env, &token.address);
+            let old_plan = create_simple_plan(&env, &client, old_price, old_days);
+            let new_plan = create_simple_plan(&env, &client, new_price, new_days);
+
+            let user = Address::generate(&env);
+            // An upgrade charges the new rate for the rest of the old term, which can span several new terms
+            let funds = old_price + new_price * (old_days / new_days + 1) as i128;
+            token.mint(&user, funds);
+            set_time(&env, 0);
+            client.subscribe(&user, &old_plan);
+
+            set_time(&env, change_at);
+            // Past the end of the term the change charges the full new price and starts over
+            client.change_plan(&user, &new_plan);
+
+            set_time(&env, change_at + cancel_delay);
+            client.cancel_subscription(&user);
+
+            let held = token.balance(&client.address);
+            prop_assert_eq!(token.balance(&user) + held, funds);
+            prop_assert!(held >= 0);
+            prop_assert_eq!(client.get_treasury_balance(&token.address), held);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use soroban_sdk::{testutils::Address as _, Address, Env, Vec};
+
+use crate::{SubscriptionContract, SubscriptionContractClient, SubscriptionTier};
+
+/// Register a subscription contract taking payments in `token`, with a 7 day grace period
+pub fn create_subscription_contract<'a>(env: &Env, token: &Address) -> SubscriptionContractClient<'a> {
+    let client = SubscriptionContractClient::new(env, &env.register(SubscriptionContract, ()));
+    client.initialize(&Address::generate(env), token, &7);
+    client
+}
+
+/// Create a monthly plan granting category 1, without family members. Requires mocked auths.
+pub fn create_simple_plan(env: &Env, client: &SubscriptionContractClient, price: i128, duration_days: u32) -> u32 {
+    client.create_plan(
+        &SubscriptionTier::Monthly,
+        &price,
+        &duration_days,
+        &Vec::from_array(env, [1]),
+        &0,
+    )
+}
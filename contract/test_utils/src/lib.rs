@@ -0,0 +1,68 @@
+//! Shared fixtures for contract tests: token setup, ledger time control and addresses.
+//!
+//! Contract crates pull this in as a dev-dependency; crate-specific client builders live
+//! behind each crate's own `testutils` feature.
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+pub const DAY: u64 = 86400;
+
+/// A Stellar asset contract with both its admin and standard token clients
+pub struct TestToken<'a> {
+    pub address: Address,
+    pub admin: token::StellarAssetClient<'a>,
+    pub client: token::TokenClient<'a>,
+}
+
+impl TestToken<'_> {
+    pub fn mint(&self, to: &Address, amount: i128) {
+        self.admin.mint(to, &amount);
+    }
+
+    pub fn balance(&self, id: &Address) -> i128 {
+        self.client.balance(id)
+    }
+}
+
+/// Environment with all auths mocked, the default for most contract tests
+pub fn setup_env() -> Env {
+    let env = Env::default();
+    env.mock_all_auths();
+    env
+}
+
+/// Register a fresh Stellar asset contract
+pub fn create_token<'a>(env: &Env) -> TestToken<'a> {
+    let issuer = Address::generate(env);
+    let address = env.register_stellar_asset_contract_v2(issuer).address();
+
+    TestToken {
+        admin: token::StellarAssetClient::new(env, &address),
+        client: token::TokenClient::new(env, &address),
+        address,
+    }
+}
+
+/// Generate `n` distinct addresses
+pub fn addresses(env: &Env, n: u32) -> std::vec::Vec<Address> {
+    (0..n).map(|_| Address::generate(env)).collect()
+}
+
+/// Set the ledger timestamp
+pub fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().set_timestamp(timestamp);
+}
+
+/// Move the ledger timestamp forward
+pub fn advance_time(env: &Env, seconds: u64) {
+    let now = env.ledger().timestamp();
+    env.ledger().set_timestamp(now + seconds);
+}
+
+/// Move the ledger timestamp forward by whole days
+pub fn advance_days(env: &Env, days: u64) {
+    advance_time(env, days * DAY);
+}
@@ -1,13 +1,17 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, Vec, IntoVal, xdr::ToXdr, Bytes};
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, Vec, xdr::ToXdr};
 
 mod merkle;
+mod params;
+mod rewards;
 mod storage;
 
 #[cfg(test)]
 mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 
-use crate::storage::{Campaign, DataKey};
+use crate::storage::{Campaign, DataKey, RewardsConfig};
 
 #[contract]
 pub struct WhitelistContract;
@@ -48,7 +52,8 @@ impl WhitelistContract {
 
         // Transfer tokens from admin to contract
         let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&campaign.admin, &env.current_contract_address(), &total_amount);
+        let contract_address = env.current_contract_address();
+        token_client.transfer(&campaign.admin, &contract_address, &total_amount);
 
         env.storage().persistent().set(&DataKey::Campaign(count), &campaign);
         env.storage().instance().set(&DataKey::CampaignCount, &count);
@@ -56,6 +61,24 @@ impl WhitelistContract {
         count
     }
 
+    pub fn set_rewards_config(env: Env, token: Address, points_per_claim: i128) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if points_per_claim < 0 {
+            panic!("invalid points per claim");
+        }
+
+        env.storage().instance().set(&DataKey::RewardsConfig, &RewardsConfig { token, points_per_claim });
+    }
+
+    pub fn set_registry(env: Env, registry: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Registry, &registry);
+    }
+
     pub fn update_root(env: Env, campaign_id: u32, new_root: BytesN<32>) {
         let mut campaign: Campaign = env.storage().persistent().get(&DataKey::Campaign(campaign_id)).expect("campaign not found");
         campaign.admin.require_auth();
@@ -154,6 +177,8 @@ impl WhitelistContract {
         let destination = recipient.unwrap_or(claimant.clone());
         let token_client = token::Client::new(&env, &campaign.token);
         token_client.transfer(&env.current_contract_address(), &destination, &amount);
+
+        rewards::award_claim_points(&env, &claimant);
     }
 
     pub fn refund(env: Env, campaign_id: u32) {
@@ -184,7 +209,7 @@ impl WhitelistContract {
 
     fn hash_leaf(env: &Env, address: &Address, amount: i128) -> BytesN<32> {
         let mut bytes = address.to_xdr(env);
-        bytes.extend(&amount.to_xdr(env));
-        env.crypto().sha256(&bytes)
+        bytes.append(&amount.to_xdr(env));
+        env.crypto().sha256(&bytes).into()
     }
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{Env, BytesN, Vec};
+use soroban_sdk::{Bytes, Env, BytesN, Vec};
 
 pub fn verify(
     env: &Env,
@@ -17,7 +17,7 @@ pub fn verify(
             data[..32].copy_from_slice(&node.to_array());
             data[32..].copy_from_slice(&computed_hash.to_array());
         }
-        computed_hash = env.crypto().sha256(&data.into());
+        computed_hash = env.crypto().sha256(&Bytes::from_array(env, &data)).into();
     }
 
     computed_hash == root
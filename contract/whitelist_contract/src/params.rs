@@ -0,0 +1,27 @@
+use soroban_sdk::{contractclient, symbol_short, Address, Env, Symbol};
+
+use crate::storage::DataKey;
+
+/// Subset of the parameter registry interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn get_i128(env: Env, namespace: Symbol, key: Symbol) -> Option<i128>;
+}
+
+const NAMESPACE: Symbol = symbol_short!("whitelist");
+const POINTS_PER_CLAIM: Symbol = symbol_short!("claim_pts");
+
+/// Points minted per claim, read from the governance registry when it sets one
+pub fn points_per_claim(env: &Env, default: i128) -> i128 {
+    read_i128(env, POINTS_PER_CLAIM).unwrap_or(default)
+}
+
+/// A registry value of the wrong type is ignored, so the local value still applies
+fn read_i128(env: &Env, key: Symbol) -> Option<i128> {
+    let registry: Address = env.storage().instance().get(&DataKey::Registry)?;
+    match RegistryClient::new(env, &registry).try_get_i128(&NAMESPACE, &key) {
+        Ok(Ok(value)) => value,
+        _ => None,
+    }
+}
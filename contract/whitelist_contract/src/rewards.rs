@@ -0,0 +1,29 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::params;
+use crate::storage::{DataKey, RewardsConfig};
+
+/// Subset of the rewards token interface used by this contract
+#[allow(dead_code)]
+#[contractclient(name = "RewardsTokenClient")]
+pub trait RewardsTokenInterface {
+    fn mint(env: Env, minter: Address, to: Address, amount: i128);
+}
+
+/// Mint loyalty points to the whitelisted claimant if a rewards token is configured
+pub fn award_claim_points(env: &Env, claimant: &Address) {
+    if let Some(config) = env
+        .storage()
+        .instance()
+        .get::<DataKey, RewardsConfig>(&DataKey::RewardsConfig)
+    {
+        let points = params::points_per_claim(env, config.points_per_claim);
+        if points > 0 {
+            RewardsTokenClient::new(env, &config.token).mint(
+                &env.current_contract_address(),
+                claimant,
+                &points,
+            );
+        }
+    }
+}
@@ -8,6 +8,8 @@ pub enum DataKey {
     Claimed(u32, Address),
     CampaignCount,
     Delegate(u32, Address), // (CampaignID, Delegator) -> Delegatee
+    RewardsConfig,
+    Registry,
 }
 
 #[contracttype]
@@ -22,3 +24,10 @@ pub struct Campaign {
     pub is_active: bool,
     pub refunded: bool,
 }
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RewardsConfig {
+    pub token: Address,
+    pub points_per_claim: i128,
+}
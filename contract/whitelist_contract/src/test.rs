@@ -1,7 +1,6 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, BytesN, Env, Vec, Bytes};
-use crate::merkle;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, BytesN, Env, IntoVal, Symbol, Vec, Bytes};
 
 // Helper to create a simple Merkle tree for testing
 // Leaf 0: (Address1, 100)
@@ -19,7 +18,7 @@ fn create_test_merkle(env: &Env, addr1: &Address, addr2: &Address) -> (BytesN<32
         data[..32].copy_from_slice(&l1.to_array());
         data[32..].copy_from_slice(&l0.to_array());
     }
-    let root = env.crypto().sha256(&data.into());
+    let root: BytesN<32> = env.crypto().sha256(&Bytes::from_array(env, &data)).into();
     
     let mut proof0 = Vec::new(env);
     proof0.push_back(l1.clone());
@@ -39,7 +38,7 @@ fn test_whitelist_flow() {
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
     
-    let contract_id = env.register_contract(None, WhitelistContract);
+    let contract_id = env.register(WhitelistContract, ());
     let client = WhitelistContractClient::new(&env, &contract_id);
     
     client.init(&admin);
@@ -80,7 +79,7 @@ fn test_delegation() {
     let delegatee = Address::generate(&env);
     let other = Address::generate(&env);
     
-    let contract_id = env.register_contract(None, WhitelistContract);
+    let contract_id = env.register(WhitelistContract, ());
     let client = WhitelistContractClient::new(&env, &contract_id);
     client.init(&admin);
 
@@ -110,7 +109,7 @@ fn test_double_claim_fails() {
     let admin = Address::generate(&env);
     let user1 = Address::generate(&env);
     
-    let contract_id = env.register_contract(None, WhitelistContract);
+    let contract_id = env.register(WhitelistContract, ());
     let client = WhitelistContractClient::new(&env, &contract_id);
     client.init(&admin);
 
@@ -133,7 +132,7 @@ fn test_refund() {
     let admin = Address::generate(&env);
     let user1 = Address::generate(&env);
     
-    let contract_id = env.register_contract(None, WhitelistContract);
+    let contract_id = env.register(WhitelistContract, ());
     let client = WhitelistContractClient::new(&env, &contract_id);
     client.init(&admin);
 
@@ -142,7 +141,7 @@ fn test_refund() {
     let token_client = token::StellarAssetClient::new(&env, &token_id);
     token_client.mint(&admin, &1000);
 
-    let (root, proof1, _) = create_test_merkle(&env, &user1, &user1);
+    let (root, _, _) = create_test_merkle(&env, &user1, &user1);
     let campaign_id = client.create_campaign(&admin, &token_id, &root, &10, &300);
 
     // Advance time past deadline (10s)
@@ -153,3 +152,159 @@ fn test_refund() {
     let token_client_token = token::Client::new(&env, &token_id);
     assert_eq!(token_client_token.balance(&admin), 1000); // Refunded full 300
 }
+
+mod mock_rewards {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockRewardsToken;
+
+    #[contractimpl]
+    impl MockRewardsToken {
+        pub fn mint(env: Env, minter: Address, to: Address, amount: i128) {
+            minter.require_auth();
+            let balance: i128 = env.storage().persistent().get(&to).unwrap_or(0);
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_claim_awards_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(WhitelistContract, ());
+    let client = WhitelistContractClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    let rewards_id = env.register(mock_rewards::MockRewardsToken, ());
+    let rewards = mock_rewards::MockRewardsTokenClient::new(&env, &rewards_id);
+    client.set_rewards_config(&rewards_id, &25);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract_v2(token_admin.clone()).address();
+    let token_client = token::StellarAssetClient::new(&env, &token_id);
+    token_client.mint(&admin, &1000);
+
+    let (root, proof1, _) = create_test_merkle(&env, &user1, &user2);
+    let campaign_id = client.create_campaign(&admin, &token_id, &root, &10000, &300);
+
+    client.claim(&campaign_id, &user1, &100, &proof1, &None);
+    assert_eq!(rewards.balance(&user1), 25);
+}
+
+mod mock_registry {
+    use soroban_sdk::{contract, contractimpl, Env, IntoVal, Symbol, Val};
+
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set(env: Env, namespace: Symbol, key: Symbol, value: Val) {
+            env.storage().persistent().set(&(namespace, key), &value);
+        }
+
+        pub fn get_i128(env: Env, namespace: Symbol, key: Symbol) -> Option<i128> {
+            env.storage()
+                .persistent()
+                .get::<_, Val>(&(namespace, key))
+                .map(|value| value.into_val(&env))
+        }
+    }
+}
+
+#[test]
+fn test_registry_overrides_claim_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let contract_id = env.register(WhitelistContract, ());
+    let client = WhitelistContractClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    let rewards_id = env.register(mock_rewards::MockRewardsToken, ());
+    let rewards = mock_rewards::MockRewardsTokenClient::new(&env, &rewards_id);
+    client.set_rewards_config(&rewards_id, &25);
+
+    let registry_id = env.register(mock_registry::MockRegistry, ());
+    let registry = mock_registry::MockRegistryClient::new(&env, &registry_id);
+    client.set_registry(&registry_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract_v2(token_admin.clone()).address();
+    let token_client = token::StellarAssetClient::new(&env, &token_id);
+    token_client.mint(&admin, &1000);
+
+    let (root, proof1, proof2) = create_test_merkle(&env, &user1, &user2);
+    let campaign_id = client.create_campaign(&admin, &token_id, &root, &10000, &300);
+
+    // A value of the wrong type is ignored
+    let ns = Symbol::new(&env, "whitelist");
+    let key = Symbol::new(&env, "claim_pts");
+    registry.set(&ns, &key, &true.into_val(&env));
+    client.claim(&campaign_id, &user1, &100, &proof1, &None);
+    assert_eq!(rewards.balance(&user1), 25);
+
+    registry.set(&ns, &key, &40i128.into_val(&env));
+    client.claim(&campaign_id, &user2, &200, &proof2, &None);
+    assert_eq!(rewards.balance(&user2), 40);
+}
+
+mod properties {
+    extern crate std;
+
+    use crate::testutils::{build_tree, create_whitelist_contract};
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+    use test_utils::{create_token, set_time, setup_env};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Whatever the allocations, a campaign never pays out more than it was funded with
+        /// and its token balance always covers the unclaimed remainder.
+        #[test]
+        fn prop_liabilities_never_exceed_balance(
+            amounts in proptest::collection::vec(1i128..10_000, 1..8),
+            total in 1i128..40_000,
+        ) {
+            let env = setup_env();
+            let token = create_token(&env);
+            let admin = Address::generate(&env);
+            let client = create_whitelist_contract(&env, &admin);
+
+            let entries: std::vec::Vec<(Address, i128)> =
+                amounts.iter().map(|amount| (Address::generate(&env), *amount)).collect();
+            let (root, proofs) = build_tree(&env, &entries);
+
+            token.mint(&admin, total);
+            set_time(&env, 0);
+            let campaign_id = client.create_campaign(&admin, &token.address, &root, &1000, &total);
+
+            for ((claimant, amount), proof) in entries.iter().zip(proofs.iter()) {
+                let _ = client.try_claim(&campaign_id, claimant, amount, proof, &None);
+
+                let campaign = client.get_campaign(&campaign_id);
+                prop_assert!(campaign.claimed_amount <= total);
+                prop_assert_eq!(token.balance(&client.address), total - campaign.claimed_amount);
+            }
+
+            set_time(&env, 1001);
+            client.refund(&campaign_id);
+            prop_assert_eq!(token.balance(&client.address), 0);
+        }
+    }
+}
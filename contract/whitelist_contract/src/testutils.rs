@@ -0,0 +1,55 @@
+extern crate std;
+
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
+
+use crate::{WhitelistContract, WhitelistContractClient};
+
+/// Register and initialize a whitelist contract
+pub fn create_whitelist_contract<'a>(env: &Env, admin: &Address) -> WhitelistContractClient<'a> {
+    let client = WhitelistContractClient::new(env, &env.register(WhitelistContract, ()));
+    client.init(admin);
+    client
+}
+
+/// Build a Merkle tree over `(claimant, amount)` entries, returning the root and
+/// each entry's proof in input order. An unpaired node is carried up a level unchanged.
+pub fn build_tree(env: &Env, entries: &[(Address, i128)]) -> (BytesN<32>, std::vec::Vec<Vec<BytesN<32>>>) {
+    let mut level: std::vec::Vec<BytesN<32>> = entries
+        .iter()
+        .map(|(claimant, amount)| WhitelistContract::hash_leaf(env, claimant, *amount))
+        .collect();
+    let mut positions: std::vec::Vec<usize> = (0..entries.len()).collect();
+    let mut proofs: std::vec::Vec<Vec<BytesN<32>>> = entries.iter().map(|_| Vec::new(env)).collect();
+
+    while level.len() > 1 {
+        for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+            if let Some(sibling) = level.get(*position ^ 1) {
+                proof.push_back(sibling.clone());
+            }
+            *position /= 2;
+        }
+
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_pair(env, left, right),
+                [single] => single.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    (level[0].clone(), proofs)
+}
+
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let mut data = [0u8; 64];
+    if a.to_array() < b.to_array() {
+        data[..32].copy_from_slice(&a.to_array());
+        data[32..].copy_from_slice(&b.to_array());
+    } else {
+        data[..32].copy_from_slice(&b.to_array());
+        data[32..].copy_from_slice(&a.to_array());
+    }
+    env.crypto().sha256(&Bytes::from_array(env, &data)).into()
+}